@@ -0,0 +1,161 @@
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote};
+use syn::{
+    parse_macro_input, Attribute, Data, DeriveInput, Error, Fields, Lit, Meta, NestedMeta, Result,
+};
+
+/// Generates a `validate(&self) -> StdResult<()>` method that checks every `Coins` field annotated
+/// with `#[coins(...)]`. Supported options:
+///
+/// * `validate` - every denom must be valid according to the Cosmos SDK's rules. Amounts need no
+///   checking, as `Coins` never holds zero amounts
+/// * `max_denoms = N` - the field may contain at most N denoms
+///
+/// Works on structs as well as enums, in which case the fields of whichever variant the message is
+/// are checked.
+#[proc_macro_derive(ValidateCoins, attributes(coins))]
+pub fn derive_validate_coins(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(input)
+        .unwrap_or_else(|err| err.to_compile_error())
+        .into()
+}
+
+fn expand(input: DeriveInput) -> Result<TokenStream2> {
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let body = match &input.data {
+        Data::Struct(data) => {
+            let (pattern, checks) = expand_fields(&data.fields)?;
+            quote! {
+                let Self #pattern = self;
+                #(#checks)*
+            }
+        }
+        Data::Enum(data) => {
+            let arms = data
+                .variants
+                .iter()
+                .map(|variant| {
+                    let ident = &variant.ident;
+                    let (pattern, checks) = expand_fields(&variant.fields)?;
+                    Ok(quote! {
+                        Self::#ident #pattern => {
+                            #(#checks)*
+                        }
+                    })
+                })
+                .collect::<Result<Vec<_>>>()?;
+            quote! {
+                match self {
+                    #(#arms)*
+                }
+            }
+        }
+        Data::Union(data) => {
+            return Err(Error::new_spanned(
+                data.union_token,
+                "ValidateCoins cannot be derived for unions",
+            ));
+        }
+    };
+
+    Ok(quote! {
+        impl #impl_generics #name #ty_generics #where_clause {
+            /// Validates all `Coins` fields annotated with `#[coins(...)]`.
+            pub fn validate(&self) -> ::cw_coins::__private::StdResult<()> {
+                #body
+                Ok(())
+            }
+        }
+    })
+}
+
+/// Returns a pattern destructuring the annotated fields, and the checks for each of them. All other
+/// fields are left unbound, so that they don't trigger unused variable warnings.
+fn expand_fields(fields: &Fields) -> Result<(TokenStream2, Vec<TokenStream2>)> {
+    let mut bindings = vec![];
+    let mut checks = vec![];
+
+    for (i, field) in fields.iter().enumerate() {
+        let (binding, label) = match &field.ident {
+            Some(ident) => (ident.clone(), ident.to_string()),
+            None => (format_ident!("__field{}", i), i.to_string()),
+        };
+
+        let options = match parse_options(&field.attrs)? {
+            Some(options) => options,
+            None => {
+                // named fields can be left out of the pattern, positional ones must be skipped
+                if field.ident.is_none() {
+                    bindings.push(quote!(_));
+                }
+                continue;
+            }
+        };
+
+        let validate = options.validate;
+        let max_denoms = match options.max_denoms {
+            Some(max) => quote!(Some(#max)),
+            None => quote!(None),
+        };
+        checks.push(quote! {
+            ::cw_coins::__private::validate_field(#binding, #label, #validate, #max_denoms)?;
+        });
+
+        bindings.push(quote!(#binding));
+    }
+
+    let pattern = match fields {
+        Fields::Named(_) => quote!({ #(#bindings,)* .. }),
+        Fields::Unnamed(_) => quote!(( #(#bindings,)* )),
+        Fields::Unit => quote!(),
+    };
+
+    Ok((pattern, checks))
+}
+
+struct Options {
+    validate: bool,
+    max_denoms: Option<usize>,
+}
+
+fn parse_options(attrs: &[Attribute]) -> Result<Option<Options>> {
+    let mut options = None;
+
+    for attr in attrs.iter().filter(|attr| attr.path.is_ident("coins")) {
+        let list = match attr.parse_meta()? {
+            Meta::List(list) => list,
+            meta => return Err(Error::new_spanned(meta, "expected `#[coins(...)]`")),
+        };
+
+        let options = options.get_or_insert(Options {
+            validate: false,
+            max_denoms: None,
+        });
+
+        for nested in list.nested {
+            match nested {
+                NestedMeta::Meta(Meta::Path(path)) if path.is_ident("validate") => {
+                    options.validate = true;
+                }
+                NestedMeta::Meta(Meta::NameValue(nv)) if nv.path.is_ident("max_denoms") => {
+                    match &nv.lit {
+                        Lit::Int(int) => options.max_denoms = Some(int.base10_parse()?),
+                        lit => return Err(Error::new_spanned(lit, "expected an integer")),
+                    }
+                }
+                nested => {
+                    return Err(Error::new_spanned(
+                        nested,
+                        "unknown option; expected `validate` or `max_denoms = N`",
+                    ));
+                }
+            }
+        }
+    }
+
+    Ok(options)
+}
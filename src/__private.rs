@@ -0,0 +1,89 @@
+//! Not public API. Used by code generated by the derive macros and by the `coins!` macro.
+use std::collections::BTreeMap;
+
+use cosmwasm_std::Uint64;
+pub use cosmwasm_std::{StdResult, Uint128};
+
+use crate::denom::validate_denom;
+use crate::{Coins, CoinsError, Denom};
+
+/// Conversion of the amounts given to the `coins!` macro, implemented for every unsigned integer
+/// type. With more than one implementation, an unsuffixed integer literal can't be inferred, so the
+/// macro casts literal amounts to `u128` itself, after checking them with `is_unsigned_literal`.
+pub trait IntoAmount {
+    fn into_amount(self) -> Uint128;
+}
+
+macro_rules! impl_into_amount {
+    ($($ty:ty),*) => {
+        $(
+            impl IntoAmount for $ty {
+                fn into_amount(self) -> Uint128 {
+                    Uint128::from(self)
+                }
+            }
+        )*
+    };
+}
+
+impl_into_amount!(u8, u16, u32, u64, u128, Uint64, Uint128);
+
+/// Returns whether a stringified literal is an unsigned decimal integer, such as `500`, `1_000` or
+/// `12345u64`, which casts to `u128` losslessly. Floats, negative numbers and booleans don't.
+pub const fn is_unsigned_literal(s: &str) -> bool {
+    let bytes = s.as_bytes();
+    if bytes.is_empty() || !bytes[0].is_ascii_digit() {
+        return false;
+    }
+
+    let mut i = 1;
+    while i < bytes.len() && (bytes[i].is_ascii_digit() || bytes[i] == b'_') {
+        i += 1;
+    }
+
+    // anything left must be an integer suffix, which the compiler checks for validity itself
+    i == bytes.len() || bytes[i] == b'u'
+}
+
+/// Builds the coins of the `coins!` macro, panicking if a denom occurs more than once or is blank,
+/// as either is a mistake in the code that invoked it. Zero amounts are left out.
+pub fn coins_from_pairs(pairs: impl IntoIterator<Item = (String, Uint128)>) -> Coins {
+    let mut map = BTreeMap::new();
+    for (denom, amount) in pairs {
+        let denom = Denom::new(denom).unwrap_or_else(|err| panic!("{}", err));
+        if map.insert(denom.clone(), amount).is_some() {
+            panic!("duplicate denom: {}", denom);
+        }
+    }
+    Coins::from_map_unchecked(map)
+}
+
+/// Checks a single field for the derived `validate`, which returns a `StdResult` so that it can be
+/// called with `?` from contract entry points, converting the error with `?` itself.
+pub fn validate_field(
+    coins: &Coins,
+    field: &str,
+    validate: bool,
+    max_denoms: Option<usize>,
+) -> Result<(), CoinsError> {
+    if let Some(max) = max_denoms {
+        if coins.len() > max {
+            return Err(CoinsError::FieldTooManyDenoms {
+                field: field.to_string(),
+                len: coins.len(),
+                max,
+            });
+        }
+    }
+
+    if validate {
+        for denom in coins.keys() {
+            validate_denom(denom).map_err(|err| CoinsError::InvalidField {
+                field: field.to_string(),
+                error: Box::new(err),
+            })?;
+        }
+    }
+
+    Ok(())
+}
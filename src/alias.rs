@@ -0,0 +1,35 @@
+use crate::{Coins, CoinsError, PerDenom};
+
+/// A map from alias denoms to the canonical denom they are equivalent to, e.g. from an `ibc/...`
+/// hash to the name of the asset it was bridged from.
+///
+/// Aliases are resolved a single level deep: the canonical denom an alias maps to is not itself
+/// looked up in the map.
+pub type AliasMap = PerDenom<String>;
+
+impl Coins {
+    /// Replaces every aliased denom with its canonical denom, summing the amounts of denoms that
+    /// consolidate into the same one. Denoms not in the alias map are kept as they are. Errors if
+    /// a summed amount overflows.
+    ///
+    /// ```rust
+    /// use std::str::FromStr;
+    /// use cw_coins::{AliasMap, Coins};
+    ///
+    /// let atom = "ibc/27394FB092D2ECCD56123C74F36E4C1F926001CEADA9CA97EA622B25F41E5EB2";
+    ///
+    /// let mut aliases = AliasMap::new();
+    /// aliases.insert(atom, "uatom".to_string()).unwrap();
+    ///
+    /// let coins = Coins::from_str(&format!("100{},23uatom", atom)).unwrap();
+    /// assert_eq!(coins.consolidate(&aliases).unwrap().to_string(), "123uatom");
+    /// ```
+    pub fn consolidate(&self, aliases: &AliasMap) -> Result<Coins, CoinsError> {
+        let mut coins = Coins::default();
+        for (denom, amount) in &self.0 {
+            let canonical = aliases.get(denom).map_or(denom.as_str(), String::as_str);
+            coins.checked_add_amount(canonical, *amount)?;
+        }
+        Ok(coins)
+    }
+}
@@ -0,0 +1,103 @@
+use cosmwasm_std::{Coin, Uint128};
+
+use crate::denom::validate_denom;
+use crate::{Coins, CoinsError, Denom, ValidationErrors};
+
+/// Collects coins one at a time, deferring all validation to `build`, which reports every problem
+/// at once rather than just the first one encountered.
+///
+/// ```rust
+/// use cosmwasm_std::coin;
+/// use cw_coins::Coins;
+///
+/// let coins = Coins::builder()
+///     .add("uatom", 12345u128)
+///     .add_coin(coin(500, "uosmo"))
+///     .build()
+///     .unwrap();
+/// assert_eq!(coins.to_string(), "12345uatom,500uosmo");
+///
+/// let err = Coins::builder()
+///     .add("uatom", 12345u128)
+///     .add("uatom", 0u128)
+///     .add("1uosmo", 500u128)
+///     .build()
+///     .unwrap_err();
+/// assert_eq!(
+///     err.to_string(),
+///     "invalid coins: duplicate denom: uatom; zero amount for uatom; invalid denom \
+///     `1uosmo`: must start with a letter",
+/// );
+/// ```
+#[derive(Clone, Default, Debug)]
+pub struct CoinsBuilder {
+    entries: Vec<(String, Uint128)>,
+}
+
+impl CoinsBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a coin of the given denom and amount.
+    pub fn add(mut self, denom: impl Into<String>, amount: impl Into<Uint128>) -> Self {
+        self.entries.push((denom.into(), amount.into()));
+        self
+    }
+
+    /// Adds a coin.
+    pub fn add_coin(self, coin: Coin) -> Self {
+        self.add(coin.denom, coin.amount)
+    }
+
+    /// Validates the collected coins, returning an error listing every invalid denom, duplicate
+    /// denom and zero amount, in the order the coins were added.
+    pub fn build(self) -> Result<Coins, ValidationErrors> {
+        let mut coins = Coins::default();
+        let mut errors = vec![];
+
+        for (denom, amount) in self.entries {
+            if coins.0.contains_key(denom.as_str()) {
+                errors.push(CoinsError::DuplicateDenom {
+                    denom: denom.clone(),
+                });
+            }
+            if amount.is_zero() {
+                errors.push(CoinsError::ZeroAmount {
+                    denom: denom.clone(),
+                });
+            }
+            if let Err(err) = validate_denom(&denom) {
+                errors.push(err);
+            }
+            if let Ok(denom) = Denom::new(denom) {
+                coins.0.insert(denom, amount);
+            }
+        }
+
+        if !errors.is_empty() {
+            return Err(ValidationErrors {
+                errors,
+            });
+        }
+
+        Ok(coins)
+    }
+}
+
+impl Coins {
+    /// Returns a builder for collecting coins with consolidated validation.
+    pub fn builder() -> CoinsBuilder {
+        CoinsBuilder::new()
+    }
+
+    /// Creates a collection from untrusted coins, e.g. the funds sent along with a message,
+    /// returning a single error listing every invalid denom, duplicate denom and zero amount, the
+    /// same way as `CoinsBuilder::build` does.
+    pub fn new_checked(coins: impl IntoIterator<Item = Coin>) -> Result<Self, ValidationErrors> {
+        coins
+            .into_iter()
+            .fold(CoinsBuilder::new(), CoinsBuilder::add_coin)
+            .build()
+    }
+}
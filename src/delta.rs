@@ -0,0 +1,56 @@
+use std::collections::BTreeSet;
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::Coins;
+
+/// A structured report of how a collection of coins changed between two snapshots, as returned by
+/// `Coins::diff`.
+#[derive(Serialize, Deserialize, Clone, Default, Debug, PartialEq, JsonSchema)]
+pub struct CoinsDelta {
+    /// Denoms held only after the change, with their new amounts
+    pub added: Coins,
+    /// Denoms held only before the change, with their old amounts
+    pub removed: Coins,
+    /// Denoms held both before and after whose amount went up, with the size of the increase
+    pub increased: Coins,
+    /// Denoms held both before and after whose amount went down, with the size of the decrease
+    pub decreased: Coins,
+}
+
+impl CoinsDelta {
+    /// Returns whether the two snapshots hold the same amounts of every denom.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty()
+            && self.removed.is_empty()
+            && self.increased.is_empty()
+            && self.decreased.is_empty()
+    }
+}
+
+impl Coins {
+    /// Compares this collection (the "before" snapshot) against another one (the "after" snapshot),
+    /// and reports which denoms were added, removed, increased or decreased.
+    pub fn diff(&self, other: &Coins) -> CoinsDelta {
+        let mut delta = CoinsDelta::default();
+
+        let denoms = self.0.keys().chain(other.0.keys()).collect::<BTreeSet<_>>();
+        for denom in denoms {
+            let (coins, amount) = match (self.0.get(denom), other.0.get(denom)) {
+                (None, Some(after)) => (&mut delta.added, *after),
+                (Some(before), None) => (&mut delta.removed, *before),
+                (Some(before), Some(after)) if after > before => {
+                    (&mut delta.increased, after - before)
+                }
+                (Some(before), Some(after)) if after < before => {
+                    (&mut delta.decreased, before - after)
+                }
+                _ => continue,
+            };
+            coins.0.insert(denom.clone(), amount);
+        }
+
+        delta
+    }
+}
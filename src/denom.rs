@@ -0,0 +1,352 @@
+use std::borrow::Borrow;
+use std::fmt;
+use std::ops::Deref;
+use std::str::FromStr;
+
+use schemars::gen::SchemaGenerator;
+use schemars::schema::Schema;
+use schemars::JsonSchema;
+use serde::{de, Serialize};
+
+use crate::parse::Key;
+use crate::{Coins, CoinsError, ValidationErrors};
+
+/// Denoms longer than this are reported by `Coins::validate`, unless they are tokenfactory denoms.
+/// It is the length of an IBC denom, the longest kind in common use.
+const SUSPICIOUS_DENOM_LEN: usize = 68;
+
+/// A denom, as used for the keys of `Coins`.
+///
+/// A denom is checked once, when it is constructed, to not be empty or consist of nothing but
+/// whitespace. Such a denom is never of any use, so unlike the other checks, this one is enforced
+/// for every denom, including those that are otherwise taken as they are, e.g. from the bank module.
+/// Whether a denom also conforms to the Cosmos SDK's rules is up to the source it comes from; check
+/// it with `validate_denom` or `validate_denom_strict` where that source is untrusted.
+///
+/// Dereferences to `str`, and compares, orders and hashes the same way as its string, so maps keyed
+/// by denoms can be looked up with plain `&str`s.
+///
+/// ```rust
+/// use cw_coins::Denom;
+///
+/// let denom: Denom = "uatom".parse().unwrap();
+/// assert_eq!(denom, "uatom");
+/// assert!(Denom::new(" ").is_err());
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Denom(String);
+
+impl Denom {
+    /// Creates a denom, erroring if it is empty or whitespace.
+    pub fn new(denom: impl Into<String>) -> Result<Self, CoinsError> {
+        let denom = denom.into();
+        if denom.trim().is_empty() {
+            return Err(CoinsError::InvalidDenom {
+                denom,
+                reason: "must not be empty or whitespace".to_string(),
+            });
+        }
+        Ok(Self(denom))
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    pub fn into_string(self) -> String {
+        self.0
+    }
+}
+
+impl Deref for Denom {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl AsRef<str> for Denom {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Borrow<str> for Denom {
+    fn borrow(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for Denom {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl FromStr for Denom {
+    type Err = CoinsError;
+
+    fn from_str(s: &str) -> Result<Self, CoinsError> {
+        Self::new(s)
+    }
+}
+
+impl TryFrom<String> for Denom {
+    type Error = CoinsError;
+
+    fn try_from(s: String) -> Result<Self, CoinsError> {
+        Self::new(s)
+    }
+}
+
+impl TryFrom<&str> for Denom {
+    type Error = CoinsError;
+
+    fn try_from(s: &str) -> Result<Self, CoinsError> {
+        Self::new(s)
+    }
+}
+
+impl From<Denom> for String {
+    fn from(denom: Denom) -> Self {
+        denom.0
+    }
+}
+
+impl PartialEq<str> for Denom {
+    fn eq(&self, other: &str) -> bool {
+        self.0 == other
+    }
+}
+
+impl PartialEq<&str> for Denom {
+    fn eq(&self, other: &&str) -> bool {
+        self.0 == *other
+    }
+}
+
+impl PartialEq<String> for Denom {
+    fn eq(&self, other: &String) -> bool {
+        &self.0 == other
+    }
+}
+
+// Serialized as a plain string, so that maps keyed by denoms serialize as JSON objects.
+impl Serialize for Denom {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.0)
+    }
+}
+
+impl<'de> de::Deserialize<'de> for Denom {
+    fn deserialize<D: de::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let Key(denom) = Key::deserialize(deserializer)?;
+        Self::new(denom).map_err(de::Error::custom)
+    }
+}
+
+impl JsonSchema for Denom {
+    fn schema_name() -> String {
+        String::schema_name()
+    }
+
+    fn json_schema(gen: &mut SchemaGenerator) -> Schema {
+        String::json_schema(gen)
+    }
+}
+
+/// Checks that a denom conforms to the Cosmos SDK's rules, i.e. matches the regex
+/// `[a-zA-Z][a-zA-Z0-9/:._-]{2,127}`:
+/// <https://github.com/cosmos/cosmos-sdk/blob/v0.46.0/types/coin.go#L854-L856>
+///
+/// Implemented by scanning the bytes by hand, since including the `regex` library would make the
+/// wasm binary too big.
+///
+/// ```rust
+/// use cw_coins::validate_denom;
+///
+/// assert!(validate_denom("uatom").is_ok());
+/// assert!(validate_denom("factory/osmo1abcd/uusdc").is_ok());
+/// assert!(validate_denom("1uatom").is_err());
+/// ```
+pub fn validate_denom(denom: &str) -> Result<(), CoinsError> {
+    let invalid = |reason: &str| {
+        Err(CoinsError::InvalidDenom {
+            denom: denom.to_string(),
+            reason: reason.to_string(),
+        })
+    };
+
+    if denom.len() < 3 || denom.len() > 128 {
+        return invalid("length must be between 3 and 128 characters");
+    }
+
+    let mut bytes = denom.bytes();
+    if !matches!(bytes.next(), Some(b) if b.is_ascii_alphabetic()) {
+        return invalid("must start with a letter");
+    }
+
+    if !bytes.all(|b| b.is_ascii_alphanumeric() || matches!(b, b'/' | b':' | b'.' | b'_' | b'-')) {
+        return invalid("contains characters other than letters, numbers or `/:._-`");
+    }
+
+    Ok(())
+}
+
+/// Checks that an IBC voucher denom, i.e. one starting with `ibc/`, is followed by the hex-encoded
+/// SHA-256 hash of its trace, in exactly 64 uppercase characters, as the IBC transfer module
+/// produces them. Denoms without the prefix are not checked.
+///
+/// A typo in the hash still makes for a denom valid per the SDK's rules, so without this check it
+/// would only surface once the bank module fails to find any such coins.
+///
+/// ```rust
+/// use cw_coins::validate_ibc_denom;
+///
+/// let atom = "ibc/27394FB092D2ECCD56123C74F36E4C1F926001CEADA9CA97EA622B25F41E5EB2";
+/// assert!(validate_ibc_denom(atom).is_ok());
+/// assert!(validate_ibc_denom(&atom.to_lowercase()).is_err());
+/// assert!(validate_ibc_denom("ibc/1234ABCD").is_err());
+/// ```
+pub fn validate_ibc_denom(denom: &str) -> Result<(), CoinsError> {
+    let hash = match denom.strip_prefix("ibc/") {
+        Some(hash) => hash,
+        None => return Ok(()),
+    };
+
+    if hash.len() != 64 || !hash.bytes().all(|b| matches!(b, b'0'..=b'9' | b'A'..=b'F')) {
+        return Err(CoinsError::InvalidDenom {
+            denom: denom.to_string(),
+            reason: "IBC hash must consist of 64 uppercase hexadecimal characters".to_string(),
+        });
+    }
+
+    Ok(())
+}
+
+/// Upper-cases the hash of an IBC denom, e.g. turning `ibc/27394fb0...` into `ibc/27394FB0...`, so
+/// that a hash typed or generated in lowercase refers to the same coins as the canonical form. Other
+/// denoms are returned as they are.
+///
+/// ```rust
+/// use cw_coins::normalize_ibc_denom;
+///
+/// assert_eq!(normalize_ibc_denom("ibc/27394fb0".to_string()), "ibc/27394FB0");
+/// assert_eq!(normalize_ibc_denom("uatom".to_string()), "uatom");
+/// ```
+pub fn normalize_ibc_denom(mut denom: String) -> String {
+    if denom.starts_with("ibc/") {
+        denom["ibc/".len()..].make_ascii_uppercase();
+    }
+    denom
+}
+
+/// Checks a denom in strict mode, i.e. against the Cosmos SDK's rules, as well as the format of IBC
+/// and tokenfactory denoms.
+pub fn validate_denom_strict(denom: &str) -> Result<(), CoinsError> {
+    validate_denom(denom)?;
+    validate_ibc_denom(denom)?;
+    validate_factory_denom(denom)
+}
+
+/// Checks that a tokenfactory denom, i.e. one starting with `factory/`, is of the form
+/// `factory/{creator}/{subdenom}`, following the rules of Osmosis' tokenfactory module:
+/// <https://github.com/osmosis-labs/osmosis/blob/v12.0.0/x/tokenfactory/types/denoms.go>
+///
+/// * the creator must look like a bech32 address, i.e. a lowercase human-readable part, the
+///   separator `1` and at least 6 characters of the bech32 alphabet, 75 characters at most in total.
+///   The checksum is not verified, as that would require the address' prefix to be known
+/// * the subdenom must be between 1 and 44 characters, consisting of letters, numbers or `/:._-`
+///
+/// Denoms without the prefix are not checked.
+///
+/// ```rust
+/// use cw_coins::validate_factory_denom;
+///
+/// assert!(validate_factory_denom("factory/osmo1qqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqq/uusdc").is_ok());
+/// assert!(validate_factory_denom("factory/Osmo1abcd/uusdc").is_err());
+/// assert!(validate_factory_denom("factory/osmo1qqqqqq").is_err());
+/// ```
+pub fn validate_factory_denom(denom: &str) -> Result<(), CoinsError> {
+    const BECH32_CHARSET: &[u8] = b"qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+
+    let rest = match denom.strip_prefix("factory/") {
+        Some(rest) => rest,
+        None => return Ok(()),
+    };
+
+    let invalid = |reason: &str| {
+        Err(CoinsError::InvalidDenom {
+            denom: denom.to_string(),
+            reason: reason.to_string(),
+        })
+    };
+
+    let (creator, subdenom) = match rest.split_once('/') {
+        Some(parts) => parts,
+        None => return invalid("must be of the form `factory/{creator}/{subdenom}`"),
+    };
+
+    // the data part can't contain `1`, so the last one is the separator
+    let well_formed = creator.len() <= 75
+        && matches!(creator.rsplit_once('1'), Some((hrp, data))
+            if !hrp.is_empty()
+                && hrp.bytes().all(|b| b.is_ascii_lowercase() || b.is_ascii_digit())
+                && data.len() >= 6
+                && data.bytes().all(|b| BECH32_CHARSET.contains(&b)));
+    if !well_formed {
+        return invalid("creator must be a bech32 address");
+    }
+
+    if subdenom.is_empty() || subdenom.len() > 44 {
+        return invalid("subdenom must be between 1 and 44 characters");
+    }
+
+    if !subdenom
+        .bytes()
+        .all(|b| b.is_ascii_alphanumeric() || matches!(b, b'/' | b':' | b'.' | b'_' | b'-'))
+    {
+        return invalid("subdenom contains characters other than letters, numbers or `/:._-`");
+    }
+
+    Ok(())
+}
+
+impl Coins {
+    /// Checks every denom in strict mode, i.e. with `validate_denom_strict`, and additionally flags
+    /// denoms that are valid but suspiciously long, for audit and migration tooling. Rather than
+    /// stopping at the first problem, all of them are returned together.
+    ///
+    /// Amounts need no checking, as `Coins` never holds zero amounts.
+    ///
+    /// ```rust
+    /// use cosmwasm_std::coin;
+    /// use cw_coins::Coins;
+    ///
+    /// let coins = Coins::from_vec(vec![coin(1, "uatom"), coin(2, "ibc/1234"), coin(3, "u$d")], false)
+    ///     .unwrap();
+    /// assert_eq!(coins.validate().unwrap_err().errors.len(), 2);
+    /// ```
+    pub fn validate(&self) -> Result<(), ValidationErrors> {
+        let mut errors = vec![];
+        for denom in self.keys() {
+            if let Err(err) = validate_denom_strict(denom) {
+                errors.push(err);
+            } else if denom.len() > SUSPICIOUS_DENOM_LEN && !denom.starts_with("factory/") {
+                errors.push(CoinsError::LongDenom {
+                    denom: denom.to_string(),
+                    len: denom.len(),
+                });
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(ValidationErrors {
+                errors,
+            })
+        }
+    }
+}
@@ -0,0 +1,319 @@
+use std::any::type_name;
+use std::fmt;
+
+use cosmwasm_std::{Decimal, DivideByZeroError, StdError, Uint128};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::Coins;
+
+/// Error returned when constructing, parsing or doing arithmetic on `Coins`. Converts into
+/// `StdError`, so it can be propagated with `?` from contract entry points.
+#[derive(Error, Clone, Debug, PartialEq, Eq)]
+pub enum CoinsError {
+    #[error("duplicate denom: {denom}")]
+    DuplicateDenom {
+        denom: String,
+    },
+
+    #[error("invalid denom `{denom}`: {reason}")]
+    InvalidDenom {
+        denom: String,
+        reason: String,
+    },
+
+    #[error("invalid amount: {amount}")]
+    InvalidAmount {
+        amount: String,
+    },
+
+    #[error("invalid coin string ({input})")]
+    Parse {
+        input: String,
+    },
+
+    #[error("zero amount for {denom}")]
+    ZeroAmount {
+        denom: String,
+    },
+
+    #[error("coins must contain at least one non-zero amount")]
+    Empty,
+
+    #[error("more than the maximum of {max} denoms")]
+    TooManyDenoms {
+        max: usize,
+    },
+
+    #[error("suspiciously long denom `{denom}` ({len} characters)")]
+    LongDenom {
+        denom: String,
+        len: usize,
+    },
+
+    #[error("overflow: cannot add {amount}{denom} to {current}{denom}")]
+    Overflow {
+        denom: String,
+        current: Uint128,
+        amount: Uint128,
+    },
+
+    #[error("overflow: cannot multiply {amount}{denom} by {factor}")]
+    MulOverflow {
+        denom: String,
+        amount: Uint128,
+        factor: String,
+    },
+
+    #[error("insufficient {denom}: available {available}, needed {needed}")]
+    Underflow {
+        denom: String,
+        needed: Uint128,
+        available: Uint128,
+    },
+
+    #[error("cannot inspect the funds sent by a {kind} message")]
+    UninspectableMsg {
+        kind: String,
+    },
+
+    #[error("cannot divide {dividend} by zero")]
+    DivideByZero {
+        dividend: String,
+    },
+
+    #[error("overflow: 10^{exp} exceeds 128 bits")]
+    ExponentOverflow {
+        exp: u32,
+    },
+
+    #[error("no ratio given for denom {denom}")]
+    MissingRatio {
+        denom: String,
+    },
+
+    #[error("cannot split coins by zero total weight")]
+    ZeroWeight,
+
+    #[error("cannot split coins into {parts} parts, more than the maximum of {max}")]
+    TooManyParts {
+        parts: usize,
+        max: usize,
+    },
+
+    #[error("fee rate {rate} is greater than one")]
+    FeeRateTooHigh {
+        rate: Decimal,
+    },
+
+    #[error("interpolation parameter {t} is greater than one")]
+    InterpolationOutOfRange {
+        t: Decimal,
+    },
+
+    #[error("unknown symbol: {symbol}")]
+    UnknownSymbol {
+        symbol: String,
+    },
+
+    #[error("amount {amount} has more than {decimals} decimal places")]
+    TooManyDecimals {
+        amount: String,
+        decimals: u32,
+    },
+
+    #[error("amount {amount}{denom} cannot be rendered in compact form without loss")]
+    LossyCompact {
+        denom: String,
+        amount: Uint128,
+    },
+
+    #[error("deposit too small to mint any shares")]
+    DepositTooSmall,
+
+    #[error("overflow: share calculation overflows for {denom}")]
+    ShareOverflow {
+        denom: String,
+    },
+
+    #[error("overflow: cannot mint {minted} shares on top of {current}")]
+    MintOverflow {
+        current: Uint128,
+        minted: Uint128,
+    },
+
+    #[error("insufficient shares of {owner}: available {available}, needed {needed}")]
+    InsufficientShares {
+        owner: String,
+        needed: Uint128,
+        available: Uint128,
+    },
+
+    #[error("`{field}` contains {len} denoms, more than the maximum of {max}")]
+    FieldTooManyDenoms {
+        field: String,
+        len: usize,
+        max: usize,
+    },
+
+    #[error("`{field}`: {error}")]
+    InvalidField {
+        field: String,
+        error: Box<CoinsError>,
+    },
+}
+
+/// Errors about malformed input convert into `StdError::ParseErr`, the rest into
+/// `StdError::GenericErr`.
+impl From<CoinsError> for StdError {
+    fn from(err: CoinsError) -> Self {
+        match err {
+            CoinsError::DuplicateDenom {
+                ..
+            }
+            | CoinsError::InvalidAmount {
+                ..
+            }
+            | CoinsError::Parse {
+                ..
+            }
+            | CoinsError::UnknownSymbol {
+                ..
+            }
+            | CoinsError::TooManyDecimals {
+                ..
+            } => StdError::parse_err(type_name::<Coins>(), err.to_string()),
+            CoinsError::DivideByZero {
+                dividend,
+            } => StdError::divide_by_zero(DivideByZeroError::new(dividend)),
+            err => StdError::generic_err(err.to_string()),
+        }
+    }
+}
+
+/// A denom for which the available amount falls short of the required amount
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct Shortfall {
+    pub denom: String,
+    pub required: Uint128,
+    pub available: Uint128,
+}
+
+/// Error returned by `Coins::covers`, listing every denom that falls short, in alphabetical order
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InsufficientFunds {
+    pub shortfalls: Vec<Shortfall>,
+}
+
+impl fmt::Display for InsufficientFunds {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let s = self
+            .shortfalls
+            .iter()
+            .map(|s| format!("{} (required {}, available {})", s.denom, s.required, s.available))
+            .collect::<Vec<_>>()
+            .join(", ");
+        write!(f, "insufficient funds: {}", s)
+    }
+}
+
+impl std::error::Error for InsufficientFunds {}
+
+impl From<InsufficientFunds> for StdError {
+    fn from(err: InsufficientFunds) -> Self {
+        StdError::generic_err(err.to_string())
+    }
+}
+
+/// Error returned by `Coins::validate`, listing every problem found in alphabetical order of denom,
+/// and by `CoinsBuilder::build`, listing them in the order the coins were added
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ValidationErrors {
+    pub errors: Vec<CoinsError>,
+}
+
+impl fmt::Display for ValidationErrors {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let s = self
+            .errors
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join("; ");
+        write!(f, "invalid coins: {}", s)
+    }
+}
+
+impl std::error::Error for ValidationErrors {}
+
+impl From<ValidationErrors> for StdError {
+    fn from(err: ValidationErrors) -> Self {
+        StdError::generic_err(err.to_string())
+    }
+}
+
+/// Error returned by `Coins::from_denoms_via`, collecting the error of every denom whose amount
+/// could not be fetched, in the order the denoms were given
+#[derive(Debug)]
+pub struct FetchErrors {
+    pub errors: Vec<(String, StdError)>,
+}
+
+impl fmt::Display for FetchErrors {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let s = self
+            .errors
+            .iter()
+            .map(|(denom, err)| format!("{}: {}", denom, err))
+            .collect::<Vec<_>>()
+            .join("; ");
+        write!(f, "failed to fetch amounts: {}", s)
+    }
+}
+
+impl std::error::Error for FetchErrors {}
+
+impl From<FetchErrors> for StdError {
+    fn from(err: FetchErrors) -> Self {
+        StdError::generic_err(err.to_string())
+    }
+}
+
+/// Error for an amount of `denom` overflowing when adding `amount` to `current`
+pub(crate) fn overflow_err(denom: &str, current: Uint128, amount: Uint128) -> CoinsError {
+    CoinsError::Overflow {
+        denom: denom.to_string(),
+        current,
+        amount,
+    }
+}
+
+/// Error for an amount of `denom` underflowing when subtracting `needed` from `available`
+pub(crate) fn underflow_err(denom: &str, available: Uint128, needed: Uint128) -> CoinsError {
+    CoinsError::Underflow {
+        denom: denom.to_string(),
+        needed,
+        available,
+    }
+}
+
+/// Error for dividing `dividend` by zero
+pub(crate) fn divide_by_zero_err(dividend: impl fmt::Display) -> CoinsError {
+    CoinsError::DivideByZero {
+        dividend: dividend.to_string(),
+    }
+}
+
+/// Error for an amount of `denom` overflowing when multiplied by `factor`
+pub(crate) fn mul_overflow_err(
+    denom: &str,
+    amount: Uint128,
+    factor: impl fmt::Display,
+) -> CoinsError {
+    CoinsError::MulOverflow {
+        denom: denom.to_string(),
+        amount,
+        factor: factor.to_string(),
+    }
+}
@@ -0,0 +1,184 @@
+use cosmwasm_std::{Coin, Uint128};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::parse::{strip_separators, Collector};
+use crate::{Coins, CoinsError, DenomValidation, ParseOptions, PerDenom};
+
+/// Display metadata of a denom, similar to the Cosmos SDK bank module's `DenomUnit`s: the symbol
+/// humans know the asset by, and the number of decimals between the display unit and the base unit.
+///
+/// E.g. for ATOM, the denom is `uatom`, the symbol `ATOM`, and decimals 6, as 1 ATOM = 10^6 uatom.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct DenomMetadata {
+    pub denom: String,
+    pub symbol: String,
+    pub decimals: u32,
+}
+
+impl Coins {
+    /// Parses a human-readable string such as `12.5 ATOM, 3 OSMO` into base-unit coins, resolving
+    /// symbols and decimals using the given metadata table. This is the counterpart of
+    /// `to_human_string`.
+    ///
+    /// Symbols are matched case-sensitively. Amounts with more fractional digits than the asset has
+    /// decimals are rejected rather than rounded, as are assets with more than 38 decimals, the
+    /// most a 128-bit amount can have. A blank string parses into empty coins.
+    pub fn from_human_str(s: &str, metadata: &[DenomMetadata]) -> Result<Self, CoinsError> {
+        if s.trim().is_empty() {
+            return Ok(Coins::default());
+        }
+        let coins = s
+            .split(',')
+            .map(|part| {
+                let mut words = part.split_whitespace();
+                let (amount, symbol) = match (words.next(), words.next(), words.next()) {
+                    (Some(amount), Some(symbol), None) => (amount, symbol),
+                    _ => {
+                        return Err(CoinsError::Parse {
+                            input: part.trim().to_string(),
+                        })
+                    }
+                };
+
+                let meta = metadata
+                    .iter()
+                    .find(|meta| meta.symbol == symbol)
+                    .ok_or_else(|| CoinsError::UnknownSymbol {
+                        symbol: symbol.to_string(),
+                    })?;
+
+                Ok(Coin {
+                    denom: meta.denom.clone(),
+                    amount: parse_decimal_amount(amount, meta.decimals)?,
+                })
+            })
+            .collect::<Result<Vec<_>, CoinsError>>()?;
+        coins.try_into()
+    }
+
+    /// Parses a compact coins string with decimal amounts, such as `1.5atom,2uosmo`, scaling every
+    /// amount by `10^exponent` of its denom into an integer amount. Denoms without an exponent in
+    /// the map have an exponent of zero, i.e. their amounts must be integers. Amounts with more
+    /// fractional digits than the exponent are rejected rather than rounded. Large amounts may be
+    /// written with `_` separators between digits, e.g. `1_000.5atom`.
+    ///
+    /// The denoms are validated against the SDK's rules, the same way as `from_str` does, so no
+    /// whitespace may separate an amount from its denom. The denoms are otherwise kept as they are. Where the display denoms differ from the base denoms, map
+    /// them with `consolidate`:
+    ///
+    /// ```rust
+    /// use cw_coins::{AliasMap, Coins, PerDenom};
+    ///
+    /// let mut exponents = PerDenom::new();
+    /// exponents.insert("atom", 6).unwrap();
+    /// let mut aliases = AliasMap::new();
+    /// aliases.insert("atom", "uatom".to_string()).unwrap();
+    ///
+    /// let coins = Coins::from_display_str("1.5atom,2uosmo", &exponents).unwrap();
+    /// assert_eq!(coins.to_string(), "1500000atom,2uosmo");
+    /// assert_eq!(coins.consolidate(&aliases).unwrap().to_string(), "1500000uatom,2uosmo");
+    /// ```
+    pub fn from_display_str(s: &str, exponents: &PerDenom<u32>) -> Result<Self, CoinsError> {
+        let options = ParseOptions {
+            denoms: DenomValidation::Sdk,
+            ..Default::default()
+        };
+        let mut collector = Collector::new(&options);
+        for part in s.split(',') {
+            let part = part.trim();
+            let split = part
+                .find(|c: char| !c.is_ascii_digit() && c != '.' && c != '_')
+                .filter(|i| *i > 0)
+                .ok_or_else(|| CoinsError::Parse {
+                    input: part.to_string(),
+                })?;
+            let (amount, denom) = part.split_at(split);
+            let exponent = exponents.get(denom).copied().unwrap_or_default();
+            let amount = strip_separators(amount).ok_or_else(|| CoinsError::InvalidAmount {
+                amount: amount.to_string(),
+            })?;
+            collector.push(denom.to_string(), parse_decimal_amount(&amount, exponent)?)?;
+        }
+        collector.finish()
+    }
+
+    /// Renders the coins in display units, e.g. `12.5 ATOM, 3 OSMO`, using the given metadata table.
+    /// Trailing zeros in the fractional part are omitted, and empty coins render as an empty string.
+    /// Errors if an asset has more than 38 decimals.
+    ///
+    /// Denoms without metadata are rendered in base units with the denom in place of a symbol, e.g.
+    /// `5 uosmo`. `from_human_str` only resolves symbols, so the result parses back into the same
+    /// coins only if the metadata table covers every denom.
+    pub fn to_human_string(&self, metadata: &[DenomMetadata]) -> Result<String, CoinsError> {
+        let parts = self
+            .0
+            .iter()
+            .map(|(denom, amount)| match metadata.iter().find(|meta| *denom == meta.denom) {
+                Some(meta) => Ok(format!(
+                    "{} {}",
+                    format_decimal_amount(*amount, meta.decimals)?,
+                    meta.symbol
+                )),
+                None => Ok(format!("{} {}", amount, denom)),
+            })
+            .collect::<Result<Vec<_>, CoinsError>>()?;
+        Ok(parts.join(", "))
+    }
+}
+
+/// The most decimals an amount can have, as `10^38` is the largest power of ten within 128 bits.
+const MAX_DECIMALS: u32 = 38;
+
+/// Errors if `decimals` exceeds `MAX_DECIMALS`, before any padding to that width is allocated.
+fn check_decimals(decimals: u32) -> Result<(), CoinsError> {
+    if decimals > MAX_DECIMALS {
+        return Err(CoinsError::ExponentOverflow {
+            exp: decimals,
+        });
+    }
+    Ok(())
+}
+
+/// Parses a decimal string such as `12.5` into an integer amount with the given number of decimals.
+pub(crate) fn parse_decimal_amount(s: &str, decimals: u32) -> Result<Uint128, CoinsError> {
+    check_decimals(decimals)?;
+    let invalid = || CoinsError::InvalidAmount {
+        amount: s.to_string(),
+    };
+
+    let (int, frac) = match s.split_once('.') {
+        Some((int, frac)) => (int, frac),
+        None => (s, ""),
+    };
+    if int.is_empty() || !int.bytes().chain(frac.bytes()).all(|b| b.is_ascii_digit()) {
+        return Err(invalid());
+    }
+    if frac.len() > decimals as usize {
+        return Err(CoinsError::TooManyDecimals {
+            amount: s.to_string(),
+            decimals,
+        });
+    }
+
+    // pad the fractional part with zeros to the full number of decimals, and parse the whole thing
+    // as one integer
+    let digits = format!("{}{:0<width$}", int, frac, width = decimals as usize);
+    digits
+        .parse::<u128>()
+        .map(Uint128::new)
+        .map_err(|_| invalid())
+}
+
+/// Formats an integer amount with the given number of decimals, omitting trailing zeros.
+pub(crate) fn format_decimal_amount(amount: Uint128, decimals: u32) -> Result<String, CoinsError> {
+    check_decimals(decimals)?;
+    let digits = format!("{:0>width$}", amount, width = decimals as usize + 1);
+    let (int, frac) = digits.split_at(digits.len() - decimals as usize);
+    let frac = frac.trim_end_matches('0');
+    if frac.is_empty() {
+        Ok(int.to_string())
+    } else {
+        Ok(format!("{}.{}", int, frac))
+    }
+}
@@ -0,0 +1,22 @@
+//! Checks of the invariants `Coins` upholds.
+//!
+//! With the `strict-invariants` feature enabled in a debug build, the collection is checked after
+//! every mutation, and a violation panics, pointing at the operation that introduced it rather than
+//! wherever the corrupt coins are used next.
+//!
+//! Without the feature, or in release builds (including wasm), the checks compile to nothing.
+//!
+//! Only amounts are checked: the denoms are kept unique and sorted by the map itself, can't be blank
+//! by construction of `Denom`, and are only validated further by constructors that are asked to.
+use crate::Coins;
+
+#[inline(always)]
+pub(crate) fn check(op: &str, coins: &Coins) {
+    #[cfg(all(feature = "strict-invariants", debug_assertions))]
+    for (denom, amount) in coins {
+        debug_assert!(!amount.is_zero(), "`{}` left a zero amount of {}", op, denom);
+    }
+
+    #[cfg(not(all(feature = "strict-invariants", debug_assertions)))]
+    let _ = (op, coins);
+}
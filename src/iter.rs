@@ -0,0 +1,244 @@
+//! Iterators over [`Coins`], all of which walk the coins in ascending order of denom.
+use std::collections::btree_map;
+use std::iter::{FromIterator, FusedIterator};
+use std::ops::{Bound, RangeBounds};
+
+use cosmwasm_std::{Coin, Uint128};
+
+use crate::parse::Collector;
+use crate::{Coins, CoinsError, Denom, ParseOptions};
+
+/// An owning iterator over the coins, yielding `Coin` values. Created by `Coins::into_iter`.
+pub struct IntoIter(btree_map::IntoIter<Denom, Uint128>);
+
+impl Iterator for IntoIter {
+    type Item = Coin;
+
+    fn next(&mut self) -> Option<Coin> {
+        self.0.next().map(|(denom, amount)| Coin {
+            denom: denom.into_string(),
+            amount,
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl IntoIterator for Coins {
+    type Item = Coin;
+    type IntoIter = IntoIter;
+
+    fn into_iter(self) -> IntoIter {
+        IntoIter(self.0.into_iter())
+    }
+}
+
+impl DoubleEndedIterator for IntoIter {
+    fn next_back(&mut self) -> Option<Coin> {
+        self.0.next_back().map(|(denom, amount)| Coin {
+            denom: denom.into_string(),
+            amount,
+        })
+    }
+}
+
+impl ExactSizeIterator for IntoIter {}
+
+impl FusedIterator for IntoIter {}
+
+/// A borrowing iterator over the coins, yielding `(denom, amount)` pairs without cloning the
+/// denoms. Created by iterating over `&Coins`.
+pub struct Entries<'a>(btree_map::Iter<'a, Denom, Uint128>);
+
+impl<'a> Iterator for Entries<'a> {
+    type Item = (&'a str, &'a Uint128);
+
+    fn next(&mut self) -> Option<(&'a str, &'a Uint128)> {
+        self.0
+            .next()
+            .map(|(denom, amount)| (denom.as_str(), amount))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl<'a> IntoIterator for &'a Coins {
+    type Item = (&'a str, &'a Uint128);
+    type IntoIter = Entries<'a>;
+
+    fn into_iter(self) -> Entries<'a> {
+        Entries(self.0.iter())
+    }
+}
+
+impl<'a> DoubleEndedIterator for Entries<'a> {
+    fn next_back(&mut self) -> Option<(&'a str, &'a Uint128)> {
+        self.0
+            .next_back()
+            .map(|(denom, amount)| (denom.as_str(), amount))
+    }
+}
+
+impl<'a> ExactSizeIterator for Entries<'a> {}
+
+impl<'a> FusedIterator for Entries<'a> {}
+
+/// A borrowing iterator over the coins, yielding `Coin` values constructed on the fly, without
+/// allocating a vector of all of them upfront. Created by `Coins::iter`.
+pub struct Iter<'a>(btree_map::Iter<'a, Denom, Uint128>);
+
+impl<'a> Iterator for Iter<'a> {
+    type Item = Coin;
+
+    fn next(&mut self) -> Option<Coin> {
+        self.0.next().map(|(denom, amount)| Coin {
+            denom: denom.to_string(),
+            amount: *amount,
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl<'a> DoubleEndedIterator for Iter<'a> {
+    fn next_back(&mut self) -> Option<Coin> {
+        self.0.next_back().map(|(denom, amount)| Coin {
+            denom: denom.to_string(),
+            amount: *amount,
+        })
+    }
+}
+
+impl<'a> ExactSizeIterator for Iter<'a> {}
+
+impl<'a> FusedIterator for Iter<'a> {}
+
+/// A borrowing iterator over the coins whose denoms lie within an interval, yielding `Coin` values.
+/// Created by `Coins::range`.
+pub struct Range<'a>(btree_map::Range<'a, Denom, Uint128>);
+
+impl<'a> Iterator for Range<'a> {
+    type Item = Coin;
+
+    fn next(&mut self) -> Option<Coin> {
+        self.0.next().map(|(denom, amount)| Coin {
+            denom: denom.to_string(),
+            amount: *amount,
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl<'a> DoubleEndedIterator for Range<'a> {
+    fn next_back(&mut self) -> Option<Coin> {
+        self.0.next_back().map(|(denom, amount)| Coin {
+            denom: denom.to_string(),
+            amount: *amount,
+        })
+    }
+}
+
+impl<'a> FusedIterator for Range<'a> {}
+
+impl Coins {
+    /// Returns an iterator over the coins as `Coin` values. To iterate without cloning the denoms,
+    /// iterate over `&Coins` instead.
+    pub fn iter(&self) -> Iter<'_> {
+        Iter(self.0.iter())
+    }
+
+    /// Returns an iterator over the coins whose denoms lie within the given interval of denoms, in
+    /// lexicographic order of bytes. E.g. all tokenfactory denoms created by a given address can be
+    /// scanned with `coins.range("factory/osmo1abcd/".."factory/osmo1abcd0")`, as `0` is the byte
+    /// following `/`.
+    ///
+    /// Panics if the start of the interval is greater than its end, or if both are excluded and
+    /// equal, the same way as `BTreeMap::range` does.
+    pub fn range<'r, R>(&self, range: R) -> Range<'_>
+    where
+        R: RangeBounds<&'r str>,
+    {
+        fn to_str<'r>(bound: Bound<&&'r str>) -> Bound<&'r str> {
+            match bound {
+                Bound::Included(s) => Bound::Included(*s),
+                Bound::Excluded(s) => Bound::Excluded(*s),
+                Bound::Unbounded => Bound::Unbounded,
+            }
+        }
+
+        let bounds = (to_str(range.start_bound()), to_str(range.end_bound()));
+        Range(self.0.range::<str, _>(bounds))
+    }
+
+    /// Consumes the coins, returning an iterator over just the denoms.
+    pub fn into_denoms(self) -> btree_map::IntoKeys<Denom, Uint128> {
+        self.0.into_keys()
+    }
+
+    /// Consumes the coins, returning an iterator over just the amounts, in order of their denoms.
+    pub fn into_amounts(self) -> btree_map::IntoValues<Denom, Uint128> {
+        self.0.into_values()
+    }
+
+    /// Collects coins from an iterator, erroring if a denom occurs more than once, the same way as
+    /// `TryFrom<Vec<Coin>>` does, or if there are more than 256 coins. Zero amounts are left out, but
+    /// still count towards duplicates and the maximum. Use `collect` instead to sum the amounts of
+    /// duplicate denoms.
+    pub fn try_from_iter(iter: impl IntoIterator<Item = Coin>) -> Result<Coins, CoinsError> {
+        Self::try_from_iter_with_options(iter, &ParseOptions::default())
+    }
+
+    /// Same as `try_from_iter`, but with the given options.
+    pub fn try_from_iter_with_options(
+        iter: impl IntoIterator<Item = Coin>,
+        options: &ParseOptions,
+    ) -> Result<Coins, CoinsError> {
+        let mut collector = Collector::new(options);
+        for coin in iter {
+            collector.push(coin.denom, coin.amount)?;
+        }
+        collector.finish()
+    }
+}
+
+/// Collecting coins sums the amounts of duplicate denoms and leaves out zero amounts, the same way
+/// as `Iterator::sum` does, and likewise panics on overflow or a non-zero coin with a blank denom. Use `Coins::try_from_iter` to reject
+/// duplicate denoms instead.
+impl FromIterator<Coin> for Coins {
+    fn from_iter<I: IntoIterator<Item = Coin>>(iter: I) -> Coins {
+        iter.into_iter().sum()
+    }
+}
+
+/// Collecting `(denom, amount)` pairs, as yielded by maps or storage range queries, behaves the same
+/// as collecting `Coin`s: the amounts of duplicate denoms are summed and zero amounts left out. Use
+/// `Coins::from_pairs` or `Coins::try_from_iter` to reject duplicate denoms instead.
+impl FromIterator<(String, Uint128)> for Coins {
+    fn from_iter<I: IntoIterator<Item = (String, Uint128)>>(iter: I) -> Coins {
+        iter.into_iter()
+            .map(|(denom, amount)| Coin {
+                denom,
+                amount,
+            })
+            .sum()
+    }
+}
+
+/// Extending coins adds the amounts of every coin, the same way as `+=` does, and likewise panics
+/// on overflow or a non-zero coin with a blank denom.
+impl Extend<Coin> for Coins {
+    fn extend<I: IntoIterator<Item = Coin>>(&mut self, iter: I) {
+        for coin in iter {
+            *self += coin;
+        }
+    }
+}
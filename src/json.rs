@@ -0,0 +1,72 @@
+//! Helpers for off-chain services that need to reproduce, byte for byte, the JSON that a contract
+//! produces for messages containing [`Coins`](crate::Coins).
+//!
+//! Contracts always serialize `Coins` with denoms in ascending order, amounts without leading
+//! zeros, and no zero amounts at all. A signing service that assembles the same message from user input may not, e.g. if the
+//! input came from a JavaScript client. [`canonicalize`] walks an arbitrary `serde_json::Value` and
+//! rewrites every Coins-shaped map into the form the contract would produce.
+//!
+//! A map is considered Coins-shaped if it is non-empty, every one of its keys is a valid denom that
+//! the caller recognizes, and every one of its values is a string that parses as an amount. A
+//! well-formed denom such as `memo` can just as well be an ordinary field name, so the caller
+//! decides which denoms to expect. Coins-shaped maps are sorted by denom explicitly; all other
+//! objects keep the key order of `serde_json::Map`, which depends on its `preserve_order` feature.
+use serde_json::{Map, Value};
+
+use crate::{parse, validate_denom};
+
+/// Recursively rewrites every Coins-shaped map within `value` into its canonical form, leaving out
+/// zero amounts, as `Coins` never holds them. A map is only considered Coins-shaped if `is_denom`
+/// accepts each of its keys.
+///
+/// ```rust
+/// use cw_coins::json::canonicalize;
+/// use serde_json::json;
+///
+/// let mut value = json!({ "funds": { "uosmo": "07", "uatom": "0" }, "memo": { "memo": "007" } });
+/// canonicalize(&mut value, |denom| denom.starts_with('u'));
+/// assert_eq!(value, json!({ "funds": { "uosmo": "7" }, "memo": { "memo": "007" } }));
+/// ```
+pub fn canonicalize(value: &mut Value, is_denom: impl Fn(&str) -> bool) {
+    canonicalize_with(value, &is_denom)
+}
+
+fn canonicalize_with(value: &mut Value, is_denom: &dyn Fn(&str) -> bool) {
+    match value {
+        Value::Object(map) => {
+            let coins = map
+                .iter()
+                .map(|(k, v)| {
+                    if !is_denom(k) || validate_denom(k).is_err() {
+                        return None;
+                    }
+                    let amount = parse::parse_amount(v.as_str()?).ok()?;
+                    Some((k.clone(), amount))
+                })
+                .collect::<Option<Vec<_>>>();
+
+            match coins {
+                Some(mut coins) if !coins.is_empty() => {
+                    coins.sort_by(|(a, _), (b, _)| a.cmp(b));
+                    *map = coins
+                        .into_iter()
+                        .filter(|(_, amount)| !amount.is_zero())
+                        .map(|(denom, amount)| (denom, Value::String(amount.to_string())))
+                        .collect::<Map<_, _>>();
+                }
+                _ => map
+                    .values_mut()
+                    .for_each(|v| canonicalize_with(v, is_denom)),
+            }
+        }
+        Value::Array(vec) => vec.iter_mut().for_each(|v| canonicalize_with(v, is_denom)),
+        _ => (),
+    }
+}
+
+/// Canonicalizes a copy of `value` and stringifies it into compact JSON.
+pub fn to_canonical_string(value: &Value, is_denom: impl Fn(&str) -> bool) -> String {
+    let mut value = value.clone();
+    canonicalize(&mut value, is_denom);
+    value.to_string()
+}
@@ -1,12 +1,62 @@
-use std::any::type_name;
-use std::collections::{BTreeMap, HashSet};
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
 use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::ops::Deref;
 use std::str::FromStr;
 
-use cosmwasm_std::{Coin, StdError, StdResult, Uint128};
+use cosmwasm_std::{Addr, Coin, StdError, StdResult, Uint128};
 use schemars::JsonSchema;
 use serde::{de, Serialize};
 
+#[doc(hidden)]
+pub mod __private;
+mod alias;
+mod builder;
+mod delta;
+mod denom;
+mod error;
+mod human;
+mod invariants;
+pub mod iter;
+#[cfg(feature = "json")]
+pub mod json;
+mod macros;
+mod math;
+#[cfg(feature = "unstable")]
+mod migrate;
+#[cfg(feature = "unstable")]
+mod msgs;
+mod non_empty;
+mod parse;
+mod per_denom;
+mod policy;
+#[cfg(feature = "unstable")]
+mod pool;
+pub mod prelude;
+mod shadow;
+
+pub use alias::AliasMap;
+pub use builder::CoinsBuilder;
+#[cfg(feature = "derive")]
+pub use cw_coins_derive::ValidateCoins;
+pub use delta::CoinsDelta;
+pub use denom::{
+    normalize_ibc_denom, validate_denom, validate_denom_strict, validate_factory_denom,
+    validate_ibc_denom, Denom,
+};
+pub use error::{CoinsError, FetchErrors, InsufficientFunds, Shortfall, ValidationErrors};
+pub use human::DenomMetadata;
+#[cfg(feature = "unstable")]
+pub use migrate::{LedgerMigration, MigrationStep};
+#[cfg(feature = "unstable")]
+pub use msgs::SpendBudget;
+pub use non_empty::NonEmptyCoins;
+pub use per_denom::PerDenom;
+pub use policy::{DenomValidation, ParseOptions, ZeroPolicy};
+#[cfg(feature = "unstable")]
+pub use pool::PooledCoins;
+
 /// A collection of coins, similar to Cosmos SDK's `sdk.Coins` struct.
 ///
 /// Differently from `sdk.Coins`, which is a vector of `sdk.Coin`, here we implement Coins as a BTreeMap
@@ -55,8 +105,62 @@ use serde::{de, Serialize};
 /// let coins = Coins::from_str("12345uatom,42069umars,88888uosmo").unwrap();
 /// let plain = coins.to_string();
 /// ```
-#[derive(Serialize, Clone, Default, Debug, PartialEq, JsonSchema)]
-pub struct Coins(pub BTreeMap<String, Uint128>);
+///
+/// ## On arithmetic
+///
+/// Coins implement the `+`, `+=`, `-` and `-=` operators, both with other Coins and with a single
+/// `Coin`, as well as `*` and `*=` with a `Uint128` scalar. Same as the operators of `Uint128`, these
+/// panic if an amount overflows or underflows. Where that is possible and must be handled gracefully,
+/// use the checked equivalents, e.g. `checked_add`, `checked_sub` and `checked_mul`, instead.
+/// Subtraction removes denoms whose amount reaches zero. Iterators of `Coin` or `Coins` can be
+/// summed with `Iterator::sum`, which panics on overflow in the same way.
+///
+/// ```rust
+/// use std::str::FromStr;
+/// use cosmwasm_std::coin;
+/// use cw_coins::Coins;
+///
+/// let coins = Coins::from_str("12345uatom").unwrap() + coin(69420, "uosmo");
+/// assert_eq!(coins.to_string(), "12345uatom,69420uosmo");
+///
+/// let max = Coins::from_str(&format!("{}uatom", u128::MAX)).unwrap();
+/// assert!(max.checked_add(&coins).is_err());
+/// ```
+///
+/// ## On iteration order
+///
+/// Every method that walks the coins, including `to_vec`, `into_vec`, the iterators in [`iter`],
+/// as well as the string and JSON representations, does so in ascending lexicographic order of
+/// denom bytes, regardless of the order the coins were inserted in. This is guaranteed, and
+/// contracts may rely on it, e.g. for producing deterministic messages or events.
+///
+/// ## On invariants
+///
+/// The underlying map can be read through `Deref`, but not mutated directly: every way of creating
+/// or changing coins goes through methods that uphold the following invariants, which the rest of
+/// the API relies on:
+///
+/// * every denom occurs at most once, and the denoms are sorted
+/// * no denom is empty or whitespace, as guaranteed by the `Denom` keys
+/// * no amount is zero; constructors leave out zero-amount coins, and arithmetic removes denoms
+///   whose amount reaches zero
+/// * denoms coming from untrusted sources without their own validation, i.e. plain text strings,
+///   raw maps and `CoinsBuilder`, are checked against the Cosmos SDK's rules
+///
+/// ```rust
+/// use cosmwasm_std::coin;
+/// use cw_coins::Coins;
+///
+/// let coins = Coins::try_from(vec![coin(100, "uatom"), coin(0, "uosmo")]).unwrap();
+/// assert_eq!(coins.to_string(), "100uatom");
+/// ```
+///
+/// ## On equality
+///
+/// Equality and hashing compare denoms and amounts exactly. As zero amounts are never stored, two
+/// collections are equal if and only if they hold the same amount of every denom.
+#[derive(Serialize, Clone, Default, Debug, PartialEq, Eq, JsonSchema)]
+pub struct Coins(BTreeMap<Denom, Uint128>);
 
 // We implement a custom serde::de::Deserialize trait to handle the case where the JSON string contains
 // duplicate keys, i.e. duplicate coin denoms.
@@ -76,18 +180,71 @@ pub struct Coins(pub BTreeMap<String, Uint128>);
 // The amount 67890 is seen the last and overwrites the two amounts seen earlier.
 //
 // This is NOT a desirable property. We want an error to be thown if the JSON string contain dups.
+//
+// Besides the map form `Coins` serializes into, the array form of `Vec<Coin>` is accepted as well,
+// i.e. `[{"denom":"uatom","amount":"12345"}]`, so that `Coins` can replace `Vec<Coin>` in existing
+// message schemas without breaking clients. Duplicates are rejected in either form. Lastly, a single
+// coin, i.e. `{"denom":"uatom","amount":"12345"}`, is accepted as one-entry `Coins`, for messages
+// where users mostly send one asset.
+//
+// `ParseOptions::storage` applies: zero amounts are left out, so that data written before they were
+// disallowed still loads, denoms are not validated, as that would cost gas every time coins are
+// loaded from storage, and there is no maximum number of entries. Coins grow without bound through
+// `+=`, `Extend` and the like, so a cap here would let anyone who can add a denom to a stored
+// balance make it fail to load. Untrusted input is capped by `Coins::deserialize_strict` or
+// `Coins::deserialize_with_options`, which are meant for message fields.
 impl<'de> de::Deserialize<'de> for Coins {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
         D: de::Deserializer<'de>,
     {
-        struct Visitor;
+        Coins::deserialize_with_options(deserializer, &ParseOptions::storage())
+    }
+}
+
+impl Coins {
+    /// Deserializes coins the same way as the `Deserialize` implementation does, but with the given
+    /// options. Unlike the `Deserialize` implementation, the default options cap the number of
+    /// entries at `ParseOptions::DEFAULT_MAX_DENOMS`.
+    pub fn deserialize_with_options<'de, D>(
+        deserializer: D,
+        options: &ParseOptions,
+    ) -> Result<Self, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        struct Visitor(ParseOptions);
+
+        // an element of the array form, i.e. a `cosmwasm_std::Coin` with its amount left unparsed
+        #[derive(serde::Deserialize)]
+        struct Entry {
+            denom: String,
+            amount: String,
+        }
+
+        fn parse_err<E: de::Error>(err: CoinsError) -> E {
+            E::custom(format!("failed to parse into Coins! {}", err))
+        }
+
+        // Tells whether the entries of a map are a single coin, i.e. a `denom` and an `amount`,
+        // returning its denom and amount if so. In the map form, the value under `denom` would be
+        // an amount, which a denom never is, as it must not start with a digit.
+        fn single_coin(entries: &[(String, String)]) -> Option<(&String, &str)> {
+            let (denom, amount) = match entries {
+                [(k1, v1), (k2, v2)] if k1 == "denom" && k2 == "amount" => (v1, v2),
+                [(k1, v1), (k2, v2)] if k1 == "amount" && k2 == "denom" => (v2, v1),
+                _ => return None,
+            };
+            parse::parse_amount(denom)
+                .is_err()
+                .then_some((denom, amount.as_str()))
+        }
 
         impl<'de> de::Visitor<'de> for Visitor {
             type Value = Coins;
 
             fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
-                f.write_str("a map with non-duplicating string keys and stringified 128-bit unsigned integer values")
+                f.write_str("a map with non-duplicating string keys and stringified 128-bit unsigned integer values, a list of coins, or a single coin")
             }
 
             #[inline]
@@ -95,101 +252,318 @@ impl<'de> de::Deserialize<'de> for Coins {
             where
                 M: de::MapAccess<'de>,
             {
-                let mut seen_denoms = HashSet::<String>::new();
-                let mut coins = BTreeMap::<String, Uint128>::new();
-
-                while let Some((denom, amount_str)) = access.next_entry::<String, String>()? {
-                    if seen_denoms.contains(&denom) {
-                        return Err(de::Error::custom(format!(
-                            "failed to parse into Coins! duplicate denom: {}",
-                            denom
-                        )));
-                    }
+                let mut collector = parse::Collector::new(&self.0);
 
-                    let amount = Uint128::from_str(&amount_str).map_err(|_| {
-                        de::Error::custom(format!(
-                            "failed to parse into Coins! invalid amount: {}",
-                            amount_str
-                        ))
-                    })?;
+                // the first two entries are held back, as they may turn out to be a single coin
+                let mut held = Some(Vec::with_capacity(2));
+                while let Some((parse::Key(denom), amount_str)) =
+                    access.next_entry::<_, String>()?
+                {
+                    let entry = (denom, amount_str);
+                    if let Some(entries) = held.as_mut().filter(|entries| entries.len() < 2) {
+                        entries.push(entry);
+                        continue;
+                    }
+                    for (denom, amount_str) in held.take().into_iter().flatten().chain([entry]) {
+                        collector.push_str(denom, &amount_str).map_err(parse_err)?;
+                    }
+                }
 
-                    seen_denoms.insert(denom.clone());
-                    coins.insert(denom, amount);
+                let held = held.unwrap_or_default();
+                if let Some((denom, amount_str)) = single_coin(&held) {
+                    collector
+                        .push_str(denom.clone(), amount_str)
+                        .map_err(parse_err)?;
+                } else {
+                    for (denom, amount_str) in held {
+                        collector.push_str(denom, &amount_str).map_err(parse_err)?;
+                    }
                 }
+                collector.finish().map_err(parse_err)
+            }
 
-                Ok(Coins(coins))
+            #[inline]
+            fn visit_seq<S>(self, mut access: S) -> Result<Self::Value, S::Error>
+            where
+                S: de::SeqAccess<'de>,
+            {
+                let mut collector = parse::Collector::new(&self.0);
+                while let Some(entry) = access.next_element::<Entry>()? {
+                    collector
+                        .push_str(entry.denom, &entry.amount)
+                        .map_err(parse_err)?;
+                }
+                collector.finish().map_err(parse_err)
             }
         }
 
-        deserializer.deserialize_map(Visitor)
+        deserializer.deserialize_any(Visitor(*options))
+    }
+
+    /// Deserializes coins, rejecting zero amounts. For use with `#[serde(deserialize_with)]` on
+    /// message fields:
+    ///
+    /// ```rust
+    /// use cw_coins::Coins;
+    /// use serde::Deserialize;
+    ///
+    /// #[derive(Deserialize)]
+    /// struct DepositMsg {
+    ///     #[serde(deserialize_with = "Coins::deserialize_rejecting_zeros")]
+    ///     funds: Coins,
+    /// }
+    ///
+    /// assert!(serde_json::from_str::<DepositMsg>(r#"{"funds":{"uatom":"0"}}"#).is_err());
+    /// ```
+    pub fn deserialize_rejecting_zeros<'de, D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        let options = ParseOptions {
+            zeros: ZeroPolicy::Reject,
+            ..Default::default()
+        };
+        Coins::deserialize_with_options(deserializer, &options)
+    }
+
+    /// Deserializes coins, rejecting zero amounts, denoms that fail `validate_denom_strict` and more
+    /// than `ParseOptions::DEFAULT_MAX_DENOMS` entries. For use with `#[serde(deserialize_with)]` on
+    /// fields of messages sent by untrusted users.
+    pub fn deserialize_strict<'de, D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        let options = ParseOptions {
+            zeros: ZeroPolicy::Reject,
+            denoms: DenomValidation::Strict,
+            ..Default::default()
+        };
+        Coins::deserialize_with_options(deserializer, &options)
     }
 }
 
+/// Same as `Coins::from_vec` in non-strict mode.
 impl TryFrom<Vec<Coin>> for Coins {
-    type Error = StdError;
+    type Error = CoinsError;
+
+    fn try_from(vec: Vec<Coin>) -> Result<Self, CoinsError> {
+        Self::from_vec(vec, false)
+    }
+}
+
+impl TryFrom<&[Coin]> for Coins {
+    type Error = CoinsError;
+
+    fn try_from(slice: &[Coin]) -> Result<Self, CoinsError> {
+        slice.to_vec().try_into()
+    }
+}
+
+impl TryFrom<&Vec<Coin>> for Coins {
+    type Error = CoinsError;
+
+    fn try_from(vec: &Vec<Coin>) -> Result<Self, CoinsError> {
+        vec.as_slice().try_into()
+    }
+}
 
-    fn try_from(vec: Vec<Coin>) -> StdResult<Self> {
-        let vec_len = vec.len();
-        let map = vec
+/// Unlike the other conversions, the map is not known to come from a source that already checked
+/// its keys, so every denom is validated. Zero amounts are left out.
+impl TryFrom<BTreeMap<String, Uint128>> for Coins {
+    type Error = CoinsError;
+
+    fn try_from(map: BTreeMap<String, Uint128>) -> Result<Self, CoinsError> {
+        let map = map
             .into_iter()
-            .map(|coin| (coin.denom, coin.amount))
-            .collect::<BTreeMap<_, _>>();
+            .map(|(denom, amount)| {
+                denom::validate_denom(&denom)?;
+                Ok((Denom::new(denom)?, amount))
+            })
+            .collect::<Result<_, CoinsError>>()?;
+        Ok(Self::from_map_unchecked(map))
+    }
+}
 
-        // the map having a different length from the vec means the vec must contain at least one
-        // duplicate denom
-        if map.len() != vec_len {
-            return Err(StdError::parse_err(type_name::<Self>(), "duplicate denoms"));
-        }
+impl<const N: usize> TryFrom<[Coin; N]> for Coins {
+    type Error = CoinsError;
 
-        Ok(Self(map))
+    fn try_from(array: [Coin; N]) -> Result<Self, CoinsError> {
+        Vec::from(array).try_into()
     }
 }
 
-impl TryFrom<&[Coin]> for Coins {
-    type Error = StdError;
+/// Converts a single coin, erroring if its denom is blank. A zero amount results in empty coins.
+impl TryFrom<Coin> for Coins {
+    type Error = CoinsError;
 
-    fn try_from(slice: &[Coin]) -> StdResult<Self> {
-        slice.to_vec().try_into()
+    fn try_from(coin: Coin) -> Result<Self, CoinsError> {
+        let denom = Denom::new(coin.denom)?;
+        Ok(Self::from_map_unchecked(BTreeMap::from([(denom, coin.amount)])))
+    }
+}
+
+impl TryFrom<&Coin> for Coins {
+    type Error = CoinsError;
+
+    fn try_from(coin: &Coin) -> Result<Self, CoinsError> {
+        coin.clone().try_into()
+    }
+}
+
+impl From<Coins> for Vec<Coin> {
+    fn from(coins: Coins) -> Self {
+        coins.into_vec()
+    }
+}
+
+impl From<Coins> for BTreeMap<String, Uint128> {
+    fn from(coins: Coins) -> Self {
+        coins.into_inner()
+    }
+}
+
+// Read-only access to the map, for lookups, ranges and the like. There is deliberately no
+// `DerefMut`, as mutating the map directly could break the invariants the methods of `Coins` uphold.
+impl Deref for Coins {
+    type Target = BTreeMap<Denom, Uint128>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl AsRef<BTreeMap<Denom, Uint128>> for Coins {
+    fn as_ref(&self) -> &BTreeMap<Denom, Uint128> {
+        &self.0
     }
 }
 
+/// Parses coins with the default `ParseOptions`, except that denoms are validated against the
+/// Cosmos SDK's rules.
+///
+/// Each coin splits into its amount and denom before the first character that is not a digit, the
+/// same way the SDK's `ParseCoinNormalized` does. A string such as `12x34uatom` is therefore 12 of
+/// the denom `x34uatom`, rather than an error: a denom may contain digits anywhere after its first
+/// letter, and refusing such strings would refuse coins the chain itself accepts. What is
+/// rejected is an amount that isn't made of digits only, as in `+12uatom` or `1.5uatom`, and a
+/// denom that breaks the SDK's rules, as in `12u@tom`.
+///
+/// ```rust
+/// use std::str::FromStr;
+/// use cw_coins::Coins;
+///
+/// let coins = Coins::from_str("12x34uatom").unwrap();
+/// assert_eq!(coins.get("x34uatom").map(|amount| amount.u128()), Some(12));
+/// assert!(Coins::from_str("1.5uatom").is_err());
+/// ```
 impl FromStr for Coins {
-    type Err = StdError;
-
-    fn from_str(s: &str) -> StdResult<Self> {
-        // `cosmwasm_std::Coin` does not implement `FromStr`, so we have do it ourselves
-        //
-        // Parsing the string with regex doesn't work, because the resulting wasm binary would be
-        // too big from including the `regex` library.
-        //
-        // If the binary size is not a concern, here's an example:
-        // https://github.com/PFC-Validator/terra-rust/blob/v1.1.8/terra-rust-api/src/client/core_types.rs#L34-L55
-        //
-        // We opt for the following solution: enumerate characters in the string, and break before
-        // the first non-number character. Split the string at that index.
-        //
-        // This assumes the denom never starts with a number, which is the case:
-        // https://github.com/cosmos/cosmos-sdk/blob/v0.46.0/types/coin.go#L854-L856
-        let parse_coin_str = |s: &str| -> StdResult<Coin> {
-            for (i, c) in s.chars().enumerate() {
-                if c.is_alphabetic() {
-                    let amount = Uint128::from_str(&s[..i])?;
-                    let denom = String::from(&s[i..]);
-                    return Ok(Coin {
-                        amount,
-                        denom,
-                    });
-                }
-            }
+    type Err = CoinsError;
 
-            Err(StdError::parse_err(type_name::<Coin>(), format!("invalid coin string ({})", s)))
+    fn from_str(s: &str) -> Result<Self, CoinsError> {
+        let options = ParseOptions {
+            denoms: DenomValidation::Sdk,
+            ..Default::default()
         };
+        Self::from_str_with(s, &options)
+    }
+}
 
-        s.split(',')
-            .into_iter()
-            .map(parse_coin_str)
-            .collect::<StdResult<Vec<_>>>()?
-            .try_into()
+impl Coins {
+    /// Parses coins from a plain text string the same way as `from_str` does, but validating the
+    /// denoms in strict mode, i.e. with `validate_denom_strict`.
+    pub fn from_str_strict(s: &str) -> Result<Self, CoinsError> {
+        let options = ParseOptions {
+            denoms: DenomValidation::Strict,
+            ..Default::default()
+        };
+        Self::from_str_with(s, &options)
+    }
+
+    /// Parses coins from a plain text string with the given options, e.g. leniently for input typed
+    /// into a CLI, or strictly for strings received on-chain. Large amounts may be written with `_`
+    /// separators between digits, as in `1_000_000uatom`.
+    ///
+    /// Note that `ParseOptions::default()` takes denoms as they are, as suits data that has been
+    /// validated before, whereas `from_str` checks them against the SDK's rules. To parse the same
+    /// way as `from_str`, set `denoms` to `DenomValidation::Sdk`.
+    ///
+    /// ```rust
+    /// use cw_coins::{Coins, DenomValidation, ParseOptions};
+    ///
+    /// let strict = ParseOptions {
+    ///     denoms: DenomValidation::Strict,
+    ///     max_denoms: 4,
+    ///     allow_empty: false,
+    ///     ..Default::default()
+    /// };
+    /// assert!(Coins::from_str_with("", &strict).is_err());
+    /// assert!(Coins::from_str_with("1ibc/1234", &strict).is_err());
+    /// assert!(Coins::from_str_with("1ibc/1234", &ParseOptions::default()).is_ok());
+    /// ```
+    pub fn from_str_with(s: &str, options: &ParseOptions) -> Result<Self, CoinsError> {
+        // Be lenient about formatting: ignore whitespace around the string and around each coin,
+        // as well as a single trailing comma. The empty string parses into empty coins, which is
+        // how both the SDK and our `Display` implementation print them.
+        let s = s.trim();
+        if s.is_empty() {
+            return parse::Collector::new(options).finish();
+        }
+        let s = s.strip_suffix(',').unwrap_or(s);
+
+        // refuse an absurd number of coins before parsing any of them
+        options.check_count(s.split(',').count())?;
+
+        let mut collector = parse::Collector::new(options);
+        for coin_str in s.split(',') {
+            let (denom, amount) = parse::parse_coin_str(coin_str.trim())?;
+            collector.push(denom, amount)?;
+        }
+        collector.finish()
+    }
+}
+
+impl TryFrom<&str> for Coins {
+    type Error = CoinsError;
+
+    fn try_from(s: &str) -> Result<Self, CoinsError> {
+        Self::from_str(s)
+    }
+}
+
+impl TryFrom<String> for Coins {
+    type Error = CoinsError;
+
+    fn try_from(s: String) -> Result<Self, CoinsError> {
+        Self::from_str(&s)
+    }
+}
+
+// Coins are partially ordered following Cosmos SDK's semantics: `a <= b` iff every amount in `a` is
+// no greater than the matching amount in `b`, with missing denoms counting as zero. If some amounts
+// are greater and others smaller, the two are incomparable.
+impl PartialOrd for Coins {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        if self == other {
+            return Some(Ordering::Equal);
+        }
+
+        match (self.is_subset(other), other.is_subset(self)) {
+            (true, false) => Some(Ordering::Less),
+            (false, true) => Some(Ordering::Greater),
+            _ => None,
+        }
+    }
+}
+
+// `Uint128` doesn't implement `Hash` in cosmwasm-std 1.0, so hash the amounts as `u128`s. This is
+// consistent with `Eq`, which compares the same denoms and amounts.
+impl Hash for Coins {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.len().hash(state);
+        for (denom, amount) in &self.0 {
+            denom.hash(state);
+            amount.u128().hash(state);
+        }
     }
 }
 
@@ -208,23 +582,137 @@ impl fmt::Display for Coins {
 }
 
 impl Coins {
+    /// An empty collection, e.g. for use as a `static` or `const` default:
+    ///
+    /// ```rust
+    /// use cw_coins::Coins;
+    ///
+    /// static NO_FEE: Coins = Coins::EMPTY;
+    /// assert!(NO_FEE.is_empty());
+    /// ```
+    pub const EMPTY: Coins = Coins(BTreeMap::new());
+
+    /// The maximum number of parts `split_evenly` splits into.
+    pub const MAX_SPLIT_PARTS: usize = 1000;
+
+    /// Creates an empty collection.
+    pub const fn new() -> Self {
+        Self::EMPTY
+    }
+
+    /// Creates a collection containing a single coin, or an empty one if the amount is zero.
+    pub fn one(denom: impl Into<String>, amount: impl Into<Uint128>) -> Self {
+        Self::new().with(denom, amount)
+    }
+
+    /// Wraps a map taken as it is, apart from leaving out zero amounts.
+    pub(crate) fn from_map_unchecked(mut map: BTreeMap<Denom, Uint128>) -> Self {
+        map.retain(|_, amount| !amount.is_zero());
+        Self(map)
+    }
+
+    /// Creates a collection from `(denom, amount)` pairs of any types that convert into denoms and
+    /// amounts, erroring if a denom occurs more than once.
+    pub fn from_pairs<I, D, A>(pairs: I) -> Result<Self, CoinsError>
+    where
+        I: IntoIterator<Item = (D, A)>,
+        D: Into<String>,
+        A: Into<Uint128>,
+    {
+        Self::try_from_iter(pairs.into_iter().map(|(denom, amount)| Coin {
+            denom: denom.into(),
+            amount: amount.into(),
+        }))
+    }
+
+    /// Converts a vector of coins, as e.g. found in `MessageInfo::funds`, erroring if it contains
+    /// duplicate denoms or more than 256 coins. Zero amounts are left out.
+    ///
+    /// Coins coming from the bank module have already been validated by the chain, so by default
+    /// their denoms are taken as they are. If `strict` is true, every denom is additionally checked
+    /// with `validate_denom_strict`, e.g. for coins supplied by users as part of a message.
+    pub fn from_vec(vec: Vec<Coin>, strict: bool) -> Result<Self, CoinsError> {
+        let options = ParseOptions {
+            denoms: if strict {
+                DenomValidation::Strict
+            } else {
+                DenomValidation::Unchecked
+            },
+            ..Default::default()
+        };
+        Self::try_from_iter_with_options(vec, &options)
+    }
+
+    /// Converts a vector of coins the same way as `TryFrom<Vec<Coin>>` does, but summing the
+    /// amounts of duplicate denoms instead of erroring, as `MessageInfo::funds` may legitimately
+    /// repeat a denom on some chains. Errors if a sum overflows, or if the vector has more than 256
+    /// coins, duplicates included.
+    pub fn try_from_vec_merging(vec: Vec<Coin>) -> Result<Self, CoinsError> {
+        ParseOptions::default().check_count(vec.len())?;
+        let mut coins = Coins::default();
+        for coin in vec {
+            coins.checked_add_amount(&coin.denom, coin.amount)?;
+        }
+        Ok(coins)
+    }
+
+    /// Returns the collection with the amount of `denom` set to `amount`, replacing any previous
+    /// amount. A zero amount removes the denom instead.
+    pub fn with(mut self, denom: impl Into<String>, amount: impl Into<Uint128>) -> Self {
+        self.set(denom, amount);
+        self
+    }
+
+    /// Returns the collection with `denom` removed.
+    pub fn without(mut self, denom: &str) -> Self {
+        self.remove(denom);
+        self
+    }
+
+    /// Sets the amount of `denom` to `amount`, returning the previous amount, if any. A zero amount
+    /// removes the denom instead.
+    ///
+    /// Panics if the denom is empty or whitespace.
+    pub fn set(&mut self, denom: impl Into<String>, amount: impl Into<Uint128>) -> Option<Uint128> {
+        let denom = Denom::new(denom).unwrap_or_else(|err| panic!("{}", err));
+
+        let amount = amount.into();
+        let previous = if amount.is_zero() {
+            self.0.remove(&denom)
+        } else {
+            self.0.insert(denom, amount)
+        };
+        invariants::check("set", self);
+        previous
+    }
+
+    /// Removes `denom` from the collection, returning its amount, if it was present.
+    pub fn remove(&mut self, denom: &str) -> Option<Uint128> {
+        self.0.remove(denom)
+    }
+
     pub fn to_vec(&self) -> Vec<Coin> {
-        self.0
-            .iter()
-            .map(|(denom, amount)| Coin {
-                denom: denom.clone(),
-                amount: *amount,
-            })
-            .collect()
+        self.iter().collect()
+    }
+
+    /// Returns the coins sorted by amount, largest first. Coins with equal amounts are ordered by
+    /// denom.
+    pub fn to_vec_sorted_by_amount(&self) -> Vec<Coin> {
+        let mut vec = self.to_vec();
+        // the sort is stable, so ties keep the denom order `to_vec` returns them in
+        vec.sort_by_key(|coin| std::cmp::Reverse(coin.amount));
+        vec
     }
 
     pub fn into_vec(self) -> Vec<Coin> {
+        self.into_iter().collect()
+    }
+
+    /// Consumes the coins, returning the underlying map from denoms to amounts.
+    pub fn into_inner(self) -> BTreeMap<String, Uint128> {
         self.0
             .into_iter()
-            .map(|(denom, amount)| Coin {
-                denom,
-                amount,
-            })
+            .map(|(denom, amount)| (denom.into_string(), amount))
             .collect()
     }
 
@@ -235,4 +723,320 @@ impl Coins {
     pub fn is_empty(&self) -> bool {
         self.0.is_empty()
     }
+
+    /// Returns whether the predicate holds for at least one of the coins.
+    pub fn any<F>(&self, mut f: F) -> bool
+    where
+        F: FnMut(&str, Uint128) -> bool,
+    {
+        self.0.iter().any(|(denom, amount)| f(denom, *amount))
+    }
+
+    /// Returns whether the predicate holds for all of the coins. Vacuously true if empty.
+    pub fn all<F>(&self, mut f: F) -> bool
+    where
+        F: FnMut(&str, Uint128) -> bool,
+    {
+        self.0.iter().all(|(denom, amount)| f(denom, *amount))
+    }
+
+    /// Returns whether every denom in this collection has an amount no greater than the matching
+    /// amount in the other collection. Denoms missing from the other collection count as zero.
+    ///
+    /// This is the canonical "do I have enough funds" check: `required.is_subset(&available)`.
+    pub fn is_subset(&self, other: &Coins) -> bool {
+        self.0
+            .iter()
+            .all(|(denom, amount)| *amount <= other.0.get(denom).copied().unwrap_or_default())
+    }
+
+    /// Returns whether the other collection is a subset of this one. See `is_subset`.
+    pub fn is_superset(&self, other: &Coins) -> bool {
+        other.is_subset(self)
+    }
+
+    /// Returns whether every denom in the other collection is present in this one with a strictly
+    /// larger amount, e.g. whether a new bid beats the previous one in every asset. Denoms only
+    /// present in this collection are disregarded. If the other collection is empty, this is
+    /// vacuously true.
+    pub fn strictly_greater_than(&self, other: &Coins) -> bool {
+        other.0.iter().all(|(denom, amount)| {
+            matches!(self.0.get(denom), Some(self_amount) if self_amount > amount)
+        })
+    }
+
+    /// Checks that this collection holds at least the `required` coins. If not, the error lists every
+    /// deficient denom along with the required and available amounts.
+    pub fn covers(&self, required: &Coins) -> Result<(), InsufficientFunds> {
+        let shortfalls = required
+            .0
+            .iter()
+            .filter_map(|(denom, required)| {
+                let available = self.0.get(denom).copied().unwrap_or_default();
+                (available < *required).then(|| Shortfall {
+                    denom: denom.to_string(),
+                    required: *required,
+                    available,
+                })
+            })
+            .collect::<Vec<_>>();
+
+        if shortfalls.is_empty() {
+            Ok(())
+        } else {
+            Err(InsufficientFunds {
+                shortfalls,
+            })
+        }
+    }
+
+    /// Returns whether the collection holds at least the given coin's amount of its denom.
+    pub fn contains_at_least(&self, coin: &Coin) -> bool {
+        self.0.get(coin.denom.as_str()).copied().unwrap_or_default() >= coin.amount
+    }
+
+    /// Renders the coins in a compact, human-readable form such as `1.2M uatom, 500 uosmo`, for use in
+    /// debug logs and events where exactness isn't needed.
+    ///
+    /// Amounts of 1000 or more are written in engineering notation with a single decimal place,
+    /// truncated so the rendered value never overstates the actual amount. Exponents up to 12 use the
+    /// suffixes K, M, B and T; larger ones are written as e.g. `e15`.
+    ///
+    /// If `strict` is true, an error is returned instead if any amount can't be rendered exactly. Use
+    /// this in consensus-relevant paths.
+    pub fn to_compact_string(&self, strict: bool) -> Result<String, CoinsError> {
+        const SUFFIXES: [&str; 4] = ["K", "M", "B", "T"];
+
+        let mut parts = vec![];
+        for (denom, amount) in &self.0 {
+            let amount = amount.u128();
+            if amount < 1000 {
+                parts.push(format!("{} {}", amount, denom));
+                continue;
+            }
+
+            let mut exp = 3u32;
+            while matches!(10u128.checked_pow(exp + 3), Some(unit) if amount >= unit) {
+                exp += 3;
+            }
+
+            let unit = 10u128.pow(exp);
+            let int = amount / unit;
+            let frac = amount % unit / (unit / 10);
+
+            if strict && int * unit + frac * (unit / 10) != amount {
+                return Err(CoinsError::LossyCompact {
+                    denom: denom.to_string(),
+                    amount: Uint128::new(amount),
+                });
+            }
+
+            let mantissa = if frac == 0 {
+                int.to_string()
+            } else {
+                format!("{}.{}", int, frac)
+            };
+            let suffix = match SUFFIXES.get(exp as usize / 3 - 1) {
+                Some(suffix) => suffix.to_string(),
+                None => format!("e{}", exp),
+            };
+            parts.push(format!("{}{} {}", mantissa, suffix, denom));
+        }
+
+        Ok(parts.join(", "))
+    }
+
+    /// Builds a collection by fetching the amount of each of the given denoms with `f`, e.g. by
+    /// querying a balance or a position through `querier`. Zero amounts are left out.
+    ///
+    /// Rather than aborting on the first failure, every denom is attempted, and the errors of all
+    /// failed ones are returned together. Each fetch is attempted once: chain queries are
+    /// deterministic within a block, so retrying a failed one inside a contract cannot succeed.
+    /// Off-chain callers wanting retries can implement them within `f`.
+    ///
+    /// ```rust
+    /// use cosmwasm_std::testing::mock_dependencies_with_balance;
+    /// use cosmwasm_std::{coin, QuerierWrapper};
+    /// use cw_coins::Coins;
+    ///
+    /// let deps = mock_dependencies_with_balance(&[coin(100, "uatom"), coin(50, "uosmo")]);
+    /// let querier = QuerierWrapper::<cosmwasm_std::Empty>::new(&deps.querier);
+    ///
+    /// let coins = Coins::from_denoms_via(querier, ["uatom", "umars"], |querier, denom| {
+    ///     Ok(querier.query_balance("cosmos2contract", denom)?.amount)
+    /// })
+    /// .unwrap();
+    /// assert_eq!(coins.to_string(), "100uatom");
+    /// ```
+    pub fn from_denoms_via<Q, D, F>(querier: Q, denoms: D, mut f: F) -> Result<Self, FetchErrors>
+    where
+        D: IntoIterator,
+        D::Item: Into<String>,
+        F: FnMut(&Q, &str) -> StdResult<Uint128>,
+    {
+        let mut coins = BTreeMap::new();
+        let mut errors = vec![];
+
+        for denom in denoms {
+            let denom = denom.into();
+            let result = Denom::new(denom.clone())
+                .map_err(StdError::from)
+                .and_then(|denom| Ok((f(&querier, &denom)?, denom)));
+            match result {
+                Ok((amount, _)) if amount.is_zero() => (),
+                Ok((amount, denom)) => {
+                    coins.insert(denom, amount);
+                }
+                Err(err) => errors.push((denom, err)),
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(Self(coins))
+        } else {
+            Err(FetchErrors {
+                errors,
+            })
+        }
+    }
+
+    /// Merges coins contributed by many sources, returning both the aggregate and each source's
+    /// contribution. Sources appearing more than once have their contributions summed. Errors if any
+    /// sum overflows.
+    pub fn merge_attributed(
+        inputs: Vec<(Addr, Coins)>,
+    ) -> Result<(Coins, BTreeMap<Addr, Coins>), CoinsError> {
+        let mut total = Coins::default();
+        let mut per_source = BTreeMap::<Addr, Coins>::new();
+
+        for (source, coins) in inputs {
+            let contribution = per_source.entry(source).or_default();
+            for (denom, amount) in &coins.0 {
+                total.checked_add_amount(denom, *amount)?;
+                contribution.checked_add_amount(denom, *amount)?;
+            }
+        }
+
+        Ok((total, per_source))
+    }
+
+    /// Returns a cheap fingerprint of the set of denoms, disregarding amounts. Two collections with
+    /// the same denoms always have the same fingerprint, so a mismatch is a quick way to detect that
+    /// the asset sets differ before doing a full comparison.
+    ///
+    /// The fingerprint is the 64-bit FNV-1a hash of the length-prefixed denoms, so it is stable across
+    /// platforms and compiler versions. It is not collision resistant, and must not be used where an
+    /// adversary could benefit from crafting a collision.
+    pub fn keys_fingerprint(&self) -> u64 {
+        const OFFSET_BASIS: u64 = 0xcbf29ce484222325;
+        const PRIME: u64 = 0x100000001b3;
+
+        self.0
+            .keys()
+            .flat_map(|denom| {
+                (denom.len() as u32)
+                    .to_be_bytes()
+                    .into_iter()
+                    .chain(denom.bytes())
+            })
+            .fold(OFFSET_BASIS, |hash, byte| (hash ^ byte as u64).wrapping_mul(PRIME))
+    }
+
+    /// Adds `amount` to the given denom, erroring if the denom is blank or the resulting amount
+    /// overflows. Adding zero to a denom not in the collection does not create a zero-amount entry.
+    pub(crate) fn checked_add_amount(
+        &mut self,
+        denom: &str,
+        amount: Uint128,
+    ) -> Result<(), CoinsError> {
+        // adding nothing is a no-op, even under a denom that could not be stored
+        if amount.is_zero() {
+            return Ok(());
+        }
+        let denom = Denom::new(denom)?;
+        let entry = self.0.entry(denom.clone()).or_default();
+        let result = entry.checked_add(amount);
+        shadow::verify_add(&denom, *entry, amount, result.as_ref().ok().copied());
+        *entry = result.map_err(|_| error::overflow_err(&denom, *entry, amount))?;
+        invariants::check("add", self);
+        Ok(())
+    }
+
+    /// Returns the element-wise maximum of the two collections, i.e. for each denom that appears in
+    /// either of them, the larger of the two amounts.
+    pub fn union(&self, other: &Coins) -> Coins {
+        self.clone().max_with(other)
+    }
+
+    /// Returns the element-wise minimum of the two collections, i.e. for each denom that appears in
+    /// both of them, the smaller of the two amounts.
+    pub fn intersection(&self, other: &Coins) -> Coins {
+        let map = self
+            .0
+            .iter()
+            .filter_map(|(denom, amount)| {
+                other
+                    .0
+                    .get(denom)
+                    .map(|other_amount| (denom.clone(), *amount.min(other_amount)))
+            })
+            .collect();
+        Coins(map)
+    }
+
+    /// Raises each amount in this collection to the matching amount in the other one, if that is
+    /// larger. Denoms present on only one side are treated as zero on the other, so they are kept
+    /// with their amount.
+    pub fn max_with(mut self, other: &Coins) -> Coins {
+        for (denom, amount) in &other.0 {
+            let entry = self.0.entry(denom.clone()).or_default();
+            if *amount > *entry {
+                *entry = *amount;
+            }
+        }
+        invariants::check("max_with", &self);
+        self
+    }
+
+    /// Lowers each amount in this collection to the matching amount in the other one, if that is
+    /// smaller. Denoms present on only one side are treated as zero on the other, so they are
+    /// dropped, as are any other denoms whose minimum is zero.
+    pub fn min_with(mut self, other: &Coins) -> Coins {
+        self.0.retain(|denom, amount| {
+            let other_amount = other.0.get(denom).copied().unwrap_or_default();
+            *amount = (*amount).min(other_amount);
+            !amount.is_zero()
+        });
+        invariants::check("min_with", &self);
+        self
+    }
+
+    /// Returns the denoms present in both collections, in alphabetical order, regardless of amounts.
+    pub fn common_denoms(&self, other: &Coins) -> Vec<&str> {
+        self.0
+            .keys()
+            .filter(|denom| other.0.contains_key(*denom))
+            .map(Denom::as_str)
+            .collect()
+    }
+
+    /// Returns the coins whose denoms are present in this collection but not in the other one.
+    /// The amounts in the other collection are disregarded.
+    pub fn difference(&self, other: &Coins) -> Coins {
+        let map = self
+            .0
+            .iter()
+            .filter(|(denom, _)| !other.0.contains_key(*denom))
+            .map(|(denom, amount)| (denom.clone(), *amount))
+            .collect();
+        Coins(map)
+    }
+
+    /// Returns the coins whose denoms are present in exactly one of the two collections, with their
+    /// amounts in that collection.
+    pub fn symmetric_difference(&self, other: &Coins) -> Coins {
+        let mut coins = self.difference(other);
+        coins.0.extend(other.difference(self).0);
+        coins
+    }
 }
@@ -0,0 +1,47 @@
+/// Builds a [`Coins`](crate::Coins) from `denom => amount` pairs, where denoms are anything that
+/// converts into a `String`, and amounts are any unsigned integers, including unsuffixed integer
+/// literals, or `Uint64`s and `Uint128`s.
+///
+/// Panics if a denom occurs more than once or is blank.
+///
+/// ```rust
+/// use cw_coins::coins;
+///
+/// use cosmwasm_std::Uint128;
+///
+/// let amount = 7u64;
+/// let coins = coins! {
+///     "uatom" => 12345,
+///     "umars" => amount,
+///     "uosmo" => Uint128::new(500),
+/// };
+/// assert_eq!(coins.to_string(), "12345uatom,7umars,500uosmo");
+/// ```
+#[macro_export]
+macro_rules! coins {
+    // literal amounts are cast to `u128`, so that unsuffixed ones needn't be inferred
+    (@pairs [$($pairs:expr,)*] $denom:expr => $amount:literal $(, $($rest:tt)*)?) => {
+        $crate::coins!(@pairs [$($pairs,)* (
+            ::std::string::String::from($denom),
+            {
+                const _: () = ::std::assert!(
+                    $crate::__private::is_unsigned_literal(::std::stringify!($amount)),
+                    "amounts must be unsigned integers",
+                );
+                $crate::__private::Uint128::new($amount as u128)
+            },
+        ),] $($($rest)*)?)
+    };
+    (@pairs [$($pairs:expr,)*] $denom:expr => $amount:expr $(, $($rest:tt)*)?) => {
+        $crate::coins!(@pairs [$($pairs,)* (
+            ::std::string::String::from($denom),
+            $crate::__private::IntoAmount::into_amount($amount),
+        ),] $($($rest)*)?)
+    };
+    (@pairs [$($pairs:expr,)*]) => {
+        $crate::__private::coins_from_pairs([$($pairs),*])
+    };
+    ($($pairs:tt)*) => {
+        $crate::coins!(@pairs [] $($pairs)*)
+    };
+}
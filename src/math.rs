@@ -0,0 +1,687 @@
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, BTreeSet};
+use std::iter::Sum;
+use std::ops::{Add, AddAssign, Mul, MulAssign, Sub, SubAssign};
+
+use cosmwasm_std::{Coin, Decimal, Uint128, Uint256};
+
+use crate::error::{divide_by_zero_err, mul_overflow_err, underflow_err};
+use crate::{invariants, shadow, Coins, CoinsError, PerDenom};
+
+impl Coins {
+    /// Adds the other collection to this one, returning an error naming the offending denom if any
+    /// amount overflows.
+    pub fn checked_add(&self, other: &Coins) -> Result<Coins, CoinsError> {
+        let mut coins = self.clone();
+        for (denom, amount) in &other.0 {
+            coins.checked_add_amount(denom, *amount)?;
+        }
+        Ok(coins)
+    }
+
+    /// Sums all collections in the iterator, returning an error naming the offending denom if any
+    /// amount overflows. Unlike `Iterator::sum`, this never panics.
+    pub fn try_sum(iter: impl IntoIterator<Item = Coins>) -> Result<Coins, CoinsError> {
+        let mut sum = Coins::default();
+        for coins in iter {
+            for (denom, amount) in &coins.0 {
+                sum.checked_add_amount(denom, *amount)?;
+            }
+        }
+        Ok(sum)
+    }
+
+    /// Adds a single coin to this collection, returning an error if the amount overflows.
+    pub fn checked_add_coin(&self, coin: &Coin) -> Result<Coins, CoinsError> {
+        let mut coins = self.clone();
+        coins.checked_add_amount(&coin.denom, coin.amount)?;
+        Ok(coins)
+    }
+
+    /// Subtracts the other collection from this one, returning an error naming the offending denom,
+    /// along with the available and needed amounts, if any amount underflows.
+    /// Denoms whose amount reaches zero are removed.
+    pub fn checked_sub(&self, other: &Coins) -> Result<Coins, CoinsError> {
+        let mut coins = self.clone();
+        for (denom, amount) in &other.0 {
+            coins.checked_sub_amount(denom, *amount)?;
+        }
+        Ok(coins)
+    }
+
+    /// Subtracts a single coin from this collection, returning an error if the amount underflows.
+    /// The denom is removed if its amount reaches zero.
+    pub fn checked_sub_coin(&self, coin: &Coin) -> Result<Coins, CoinsError> {
+        let mut coins = self.clone();
+        coins.checked_sub_amount(&coin.denom, coin.amount)?;
+        Ok(coins)
+    }
+
+    /// Adds every amount of the other collection that doesn't overflow to this one, leaving the
+    /// amounts that would overflow unchanged. Rather than aborting on the first error, this reports,
+    /// for every denom of `other`, either the resulting amount or why it could not be added, e.g. to
+    /// settle a batch in which one bad entry shouldn't hide the outcome of the others.
+    pub fn add_many(&mut self, other: &Coins) -> PerDenom<Result<Uint128, CoinsError>> {
+        let report = other
+            .0
+            .iter()
+            .map(|(denom, amount)| {
+                let result = self
+                    .checked_add_amount(denom, *amount)
+                    .map(|_| self.0.get(denom).copied().unwrap_or_default());
+                (denom.clone(), result)
+            })
+            .collect();
+        PerDenom::from_map_unchecked(report)
+    }
+
+    /// Deducts every amount of the other collection that is available from this one, leaving the
+    /// amounts that would underflow unchanged, and reports the outcome for every denom of `other`
+    /// the same way as `add_many` does. A resulting amount of zero means the denom was removed.
+    pub fn deduct_many(&mut self, other: &Coins) -> PerDenom<Result<Uint128, CoinsError>> {
+        let report = other
+            .0
+            .iter()
+            .map(|(denom, amount)| {
+                let result = self
+                    .checked_sub_amount(denom, *amount)
+                    .map(|_| self.0.get(denom).copied().unwrap_or_default());
+                (denom.clone(), result)
+            })
+            .collect();
+        PerDenom::from_map_unchecked(report)
+    }
+
+    /// Subtracts the other collection from this one, flooring every amount at zero instead of
+    /// erroring. Denoms whose amount reaches zero are removed.
+    pub fn saturating_sub(&self, other: &Coins) -> Coins {
+        let mut coins = self.clone();
+        coins.0.retain(|denom, amount| {
+            if let Some(other_amount) = other.0.get(denom) {
+                let result = amount.saturating_sub(*other_amount);
+                shadow::verify_saturating_sub(denom, *amount, *other_amount, result);
+                *amount = result;
+            }
+            !amount.is_zero()
+        });
+        invariants::check("saturating_sub", &coins);
+        coins
+    }
+
+    /// Subtracts as much of the other collection from this one as is available, returning the
+    /// result along with the remainder of `other` that could not be covered, e.g. to partially fill
+    /// an order. The amounts actually deducted are `other` minus the remainder. Denoms whose amount
+    /// reaches zero are left out of the respective collection.
+    pub fn sub_with_remainder(&self, other: &Coins) -> (Coins, Coins) {
+        let mut coins = self.clone();
+        let mut remainder = Coins::default();
+        for (denom, amount) in &other.0 {
+            let available = coins.0.get(denom).copied().unwrap_or_default();
+            if available > *amount {
+                let left = available - *amount;
+                shadow::verify_sub(denom, available, *amount, Some(left));
+                coins.0.insert(denom.clone(), left);
+            } else {
+                coins.0.remove(denom);
+                if *amount > available {
+                    let short = *amount - available;
+                    shadow::verify_sub(denom, *amount, available, Some(short));
+                    remainder.0.insert(denom.clone(), short);
+                }
+            }
+        }
+        invariants::check("sub_with_remainder", &coins);
+        invariants::check("sub_with_remainder", &remainder);
+        (coins, remainder)
+    }
+
+    /// Returns the absolute difference between the amounts of every denom in either collection,
+    /// treating missing denoms as zero. Denoms whose amounts are equal are left out, so the result
+    /// is empty if and only if the two collections are equal, zero amounts aside.
+    pub fn abs_diff(&self, other: &Coins) -> Coins {
+        let denoms = self.0.keys().chain(other.0.keys()).collect::<BTreeSet<_>>();
+
+        let mut coins = Coins::default();
+        for denom in denoms {
+            let a = self.0.get(denom).copied().unwrap_or_default();
+            let b = other.0.get(denom).copied().unwrap_or_default();
+            let diff = if a > b {
+                a - b
+            } else {
+                b - a
+            };
+            shadow::verify_abs_diff(denom, a, b, diff);
+            if !diff.is_zero() {
+                coins.0.insert(denom.clone(), diff);
+            }
+        }
+        invariants::check("abs_diff", &coins);
+        coins
+    }
+
+    /// Multiplies every amount by `factor`, returning an error naming the offending denom if any
+    /// amount overflows. Multiplying by zero results in an empty collection.
+    pub fn checked_mul(&self, factor: impl Into<Uint128>) -> Result<Coins, CoinsError> {
+        let factor = factor.into();
+        let mut coins = Coins::default();
+        for (denom, amount) in &self.0 {
+            let result = amount.checked_mul(factor);
+            shadow::verify_mul(denom, *amount, factor, result.as_ref().ok().copied());
+            let product = result.map_err(|_| mul_overflow_err(denom, *amount, factor))?;
+            if !product.is_zero() {
+                coins.0.insert(denom.clone(), product);
+            }
+        }
+        invariants::check("checked_mul", &coins);
+        Ok(coins)
+    }
+
+    /// Multiplies every amount by `numerator / denominator`, rounding down, in the same way as
+    /// `Uint128::multiply_ratio`. The intermediate product is computed in 256 bits, so this only
+    /// errors if the final result overflows, or if `denominator` is zero. Amounts that round to zero
+    /// are removed.
+    pub fn checked_mul_ratio(
+        &self,
+        numerator: impl Into<Uint128>,
+        denominator: impl Into<Uint128>,
+    ) -> Result<Coins, CoinsError> {
+        let numerator = numerator.into();
+        let denominator = denominator.into();
+        if denominator.is_zero() {
+            return Err(divide_by_zero_err(self));
+        }
+
+        let mut coins = Coins::default();
+        for (denom, amount) in &self.0 {
+            let product = mul_ratio(denom, *amount, numerator, denominator)?;
+            if !product.is_zero() {
+                coins.0.insert(denom.clone(), product);
+            }
+        }
+        invariants::check("checked_mul_ratio", &coins);
+        Ok(coins)
+    }
+
+    /// Multiplies the amount of every denom by its own `(numerator, denominator)` ratio, rounding
+    /// down, e.g. to apply per-asset collateral factors. Amounts that round to zero are removed.
+    /// Errors if any amount overflows, if any denominator is zero, or if a denom has no ratio, so
+    /// that an unlisted asset is never silently counted at its full amount.
+    pub fn apply_ratios(
+        &self,
+        ratios: &BTreeMap<String, (Uint128, Uint128)>,
+    ) -> Result<Coins, CoinsError> {
+        let mut coins = Coins::default();
+        for (denom, amount) in &self.0 {
+            let (numerator, denominator) =
+                ratios
+                    .get(denom.as_str())
+                    .ok_or_else(|| CoinsError::MissingRatio {
+                        denom: denom.to_string(),
+                    })?;
+            let product = mul_ratio(denom, *amount, *numerator, *denominator)?;
+            if !product.is_zero() {
+                coins.0.insert(denom.clone(), product);
+            }
+        }
+        invariants::check("apply_ratios", &coins);
+        Ok(coins)
+    }
+
+    /// Same as `checked_mul_ratio`, but taking a 256-bit numerator and denominator, e.g. the atomics
+    /// of a `Decimal256` price and its fractional unit. Every intermediate product is computed in
+    /// 256 bits before narrowing back, so this errors only if that product exceeds 256 bits, if the
+    /// final result exceeds 128 bits, or if `denominator` is zero.
+    pub fn checked_mul_wide(
+        &self,
+        numerator: Uint256,
+        denominator: Uint256,
+    ) -> Result<Coins, CoinsError> {
+        if denominator.is_zero() {
+            return Err(divide_by_zero_err(self));
+        }
+
+        let mut coins = Coins::default();
+        for (denom, amount) in &self.0 {
+            let product = Uint256::from(*amount)
+                .checked_mul(numerator)
+                .ok()
+                .map(|product| product / denominator)
+                .and_then(|product| Uint128::try_from(product).ok());
+            shadow::verify_mul_wide(
+                "mul_wide",
+                denom,
+                *amount,
+                numerator,
+                denominator,
+                false,
+                product,
+            );
+            let product = product.ok_or_else(|| {
+                mul_overflow_err(denom, *amount, format!("{}/{}", numerator, denominator))
+            })?;
+            if !product.is_zero() {
+                coins.0.insert(denom.clone(), product);
+            }
+        }
+        invariants::check("checked_mul_wide", &coins);
+        Ok(coins)
+    }
+
+    /// Multiplies every amount by `factor` in 256 bits, which can never overflow. The products are
+    /// returned as is, for further wide arithmetic before narrowing back, e.g. with
+    /// `Uint128::try_from`.
+    pub fn full_mul(&self, factor: impl Into<Uint128>) -> PerDenom<Uint256> {
+        let factor = factor.into();
+        PerDenom::from_map_unchecked(
+            self.0
+                .iter()
+                .map(|(denom, amount)| (denom.clone(), amount.full_mul(factor)))
+                .filter(|(_, product)| !product.is_zero())
+                .collect(),
+        )
+    }
+
+    /// Divides every amount by `divisor`, returning the quotient along with the remainder, so that
+    /// `quotient * divisor + remainder` always equals the original collection. Denoms whose
+    /// quotient or remainder is zero are left out of the respective collection. Errors if the
+    /// divisor is zero.
+    pub fn checked_div(&self, divisor: u128) -> Result<(Coins, Coins), CoinsError> {
+        if divisor == 0 {
+            return Err(divide_by_zero_err(self));
+        }
+
+        let divisor = Uint128::new(divisor);
+        let mut quotient = Coins::default();
+        let mut remainder = Coins::default();
+        for (denom, amount) in &self.0 {
+            let q = *amount / divisor;
+            let r = *amount % divisor;
+            shadow::verify_div(denom, *amount, divisor, q, r);
+            if !q.is_zero() {
+                quotient.0.insert(denom.clone(), q);
+            }
+            if !r.is_zero() {
+                remainder.0.insert(denom.clone(), r);
+            }
+        }
+        invariants::check("checked_div", &quotient);
+        invariants::check("checked_div", &remainder);
+        Ok((quotient, remainder))
+    }
+
+    /// Splits the collection into `n` equal parts, returning the parts along with the remainder that
+    /// cannot be split evenly. Errors if `n` is zero or greater than `MAX_SPLIT_PARTS`, as every
+    /// part is allocated up front.
+    pub fn split_evenly(&self, n: usize) -> Result<(Vec<Coins>, Coins), CoinsError> {
+        if n > Self::MAX_SPLIT_PARTS {
+            return Err(CoinsError::TooManyParts {
+                parts: n,
+                max: Self::MAX_SPLIT_PARTS,
+            });
+        }
+
+        let (part, remainder) = self.checked_div(n as u128)?;
+        Ok((vec![part; n], remainder))
+    }
+
+    /// Multiplies every amount by `10^exp`, e.g. to convert display units into base units. Errors if
+    /// any amount overflows.
+    pub fn scale_up(&self, exp: u32) -> Result<Coins, CoinsError> {
+        self.checked_mul(Uint128::new(pow10(exp)?))
+    }
+
+    /// Divides every amount by `10^exp`, e.g. to convert base units into display units. As the
+    /// division rounds down, the amounts that were cut off are returned as the remainder alongside
+    /// the scaled collection, the same way as `checked_div` does.
+    pub fn scale_down(&self, exp: u32) -> Result<(Coins, Coins), CoinsError> {
+        self.checked_div(pow10(exp)?)
+    }
+
+    /// Multiplies every amount by a decimal, rounding down. Amounts that round to zero are removed.
+    /// Errors if any amount overflows, which is only possible if the decimal is greater than one.
+    pub fn mul_floor(&self, ratio: Decimal) -> Result<Coins, CoinsError> {
+        self.mul_decimal(ratio, false)
+    }
+
+    /// Multiplies every amount by a decimal, rounding up. Errors if any amount overflows, which is
+    /// only possible if the decimal is greater than one.
+    pub fn mul_ceil(&self, ratio: Decimal) -> Result<Coins, CoinsError> {
+        self.mul_decimal(ratio, true)
+    }
+
+    fn mul_decimal(&self, ratio: Decimal, round_up: bool) -> Result<Coins, CoinsError> {
+        // a decimal is stored as an integer number of atomics, `Decimal::one()` being 10^18 of them,
+        // so amount * ratio = amount * atomics / 10^18. The intermediate product always fits in 256
+        // bits, so only the final result can overflow
+        let unit = Uint256::from(Decimal::one().atomics());
+
+        let mut coins = Coins::default();
+        for (denom, amount) in &self.0 {
+            let numerator = amount.full_mul(ratio.atomics());
+            let mut product = numerator / unit;
+            if round_up && !(numerator % unit).is_zero() {
+                product += Uint256::from(1u8);
+            }
+
+            let product = Uint128::try_from(product).ok();
+            shadow::verify_mul_wide(
+                "mul_decimal",
+                denom,
+                *amount,
+                Uint256::from(ratio.atomics()),
+                unit,
+                round_up,
+                product,
+            );
+            let product = product.ok_or_else(|| mul_overflow_err(denom, *amount, ratio))?;
+            if !product.is_zero() {
+                coins.0.insert(denom.clone(), product);
+            }
+        }
+        invariants::check("mul_decimal", &coins);
+        Ok(coins)
+    }
+
+    /// Splits the collection into one part per weight, each part receiving every amount
+    /// proportionally to its weight relative to the sum of all weights. The weights need not sum to
+    /// one.
+    ///
+    /// Each share is first rounded down. The leftover dust of every denom, which is always less than
+    /// the number of non-zero weights, is then handed out one unit at a time to the parts with the
+    /// largest weights, ties going to the part that comes first. Parts with a zero weight therefore
+    /// always come out empty, and the parts always sum back to the original collection.
+    ///
+    /// Errors if no weights are given or if they are all zero.
+    pub fn split_weighted(&self, weights: &[Decimal]) -> Result<Vec<Coins>, CoinsError> {
+        let total_weight = weights
+            .iter()
+            .fold(Uint256::zero(), |acc, weight| acc + Uint256::from(weight.atomics()));
+        if total_weight.is_zero() {
+            return Err(CoinsError::ZeroWeight);
+        }
+
+        // indexes of the parts that receive the leftover dust, in order of priority
+        let mut dust_order = (0..weights.len()).collect::<Vec<_>>();
+        dust_order.sort_by_key(|i| Reverse(weights[*i]));
+
+        let mut parts = vec![Coins::default(); weights.len()];
+        for (denom, amount) in &self.0 {
+            let mut shares = weights
+                .iter()
+                .map(|weight| {
+                    // a weight never exceeds the total weight, so the share always fits in 128 bits
+                    let share = amount.full_mul(weight.atomics()) / total_weight;
+                    let share = Uint128::try_from(share).unwrap();
+                    shadow::verify_mul_wide(
+                        "split_weighted",
+                        denom,
+                        *amount,
+                        Uint256::from(weight.atomics()),
+                        total_weight,
+                        false,
+                        Some(share),
+                    );
+                    share
+                })
+                .collect::<Vec<_>>();
+
+            let distributed = shares
+                .iter()
+                .fold(Uint128::zero(), |acc, share| acc + share);
+            let dust = (*amount - distributed).u128() as usize;
+            for i in dust_order.iter().take(dust) {
+                shares[*i] += Uint128::new(1);
+            }
+
+            for (part, share) in parts.iter_mut().zip(shares) {
+                if !share.is_zero() {
+                    part.0.insert(denom.clone(), share);
+                }
+            }
+        }
+        for part in &parts {
+            invariants::check("split_weighted", part);
+        }
+        Ok(parts)
+    }
+
+    /// Charges a fee of `rate` over every amount, returning the fee along with the net amounts that
+    /// remain. The fee is rounded down, so users are never overcharged. Errors if the rate is
+    /// greater than one.
+    pub fn apply_fee(&self, rate: Decimal) -> Result<(Coins, Coins), CoinsError> {
+        if rate > Decimal::one() {
+            return Err(CoinsError::FeeRateTooHigh {
+                rate,
+            });
+        }
+
+        let fee = self.mul_floor(rate)?;
+        let net = self.checked_sub(&fee)?;
+        Ok((fee, net))
+    }
+
+    /// Interpolates linearly between `start` and `end`, where `t` of zero returns `start` and `t` of
+    /// one returns `end`. Denoms missing from either collection count as zero. The change from
+    /// `start` is rounded down, so e.g. a vesting schedule never releases more than is due. Errors
+    /// if `t` is greater than one.
+    pub fn lerp(start: &Coins, end: &Coins, t: Decimal) -> Result<Coins, CoinsError> {
+        if t > Decimal::one() {
+            return Err(CoinsError::InterpolationOutOfRange {
+                t,
+            });
+        }
+
+        let denoms = start.0.keys().chain(end.0.keys()).collect::<BTreeSet<_>>();
+
+        let mut coins = Coins::default();
+        for denom in denoms {
+            let from = start.0.get(denom).copied().unwrap_or_default();
+            let to = end.0.get(denom).copied().unwrap_or_default();
+            let amount = if to >= from {
+                from + (to - from) * t
+            } else {
+                from - (from - to) * t
+            };
+            shadow::verify_lerp(denom, from, to, t, amount);
+            if !amount.is_zero() {
+                coins.0.insert(denom.clone(), amount);
+            }
+        }
+        invariants::check("lerp", &coins);
+        Ok(coins)
+    }
+
+    /// Subtracts `amount` from the given denom, erroring if the amount underflows. Denoms not in the
+    /// collection count as zero. If the resulting amount is zero, the denom is removed.
+    pub(crate) fn checked_sub_amount(
+        &mut self,
+        denom: &str,
+        amount: Uint128,
+    ) -> Result<(), CoinsError> {
+        if amount.is_zero() {
+            return Ok(());
+        }
+
+        let available = self.0.get(denom).copied().unwrap_or_default();
+        let result = available.checked_sub(amount);
+        shadow::verify_sub(denom, available, amount, result.as_ref().ok().copied());
+
+        match result {
+            Ok(remaining) if remaining.is_zero() => {
+                self.0.remove(denom);
+            }
+            Ok(remaining) => {
+                // a non-zero amount remains, so the denom was there to begin with
+                if let Some(entry) = self.0.get_mut(denom) {
+                    *entry = remaining;
+                }
+            }
+            Err(_) => return Err(underflow_err(denom, available, amount)),
+        }
+
+        invariants::check("sub", self);
+        Ok(())
+    }
+}
+
+fn mul_ratio(
+    denom: &str,
+    amount: Uint128,
+    numerator: Uint128,
+    denominator: Uint128,
+) -> Result<Uint128, CoinsError> {
+    if denominator.is_zero() {
+        return Err(divide_by_zero_err(format!("{}{}", amount, denom)));
+    }
+
+    let result = amount.checked_multiply_ratio(numerator, denominator);
+    shadow::verify_mul_ratio(denom, amount, numerator, denominator, result.as_ref().ok().copied());
+    result.map_err(|_| mul_overflow_err(denom, amount, format!("{}/{}", numerator, denominator)))
+}
+
+fn pow10(exp: u32) -> Result<u128, CoinsError> {
+    10u128.checked_pow(exp).ok_or(CoinsError::ExponentOverflow {
+        exp,
+    })
+}
+
+// The arithmetic operators panic on overflow, the same way the operators of `Uint128` do, and when
+// adding a non-zero `Coin` with a blank denom, which cannot be stored. Use `checked_add` and
+// friends where either is possible and must be handled gracefully.
+
+impl Add<&Coins> for Coins {
+    type Output = Coins;
+
+    fn add(mut self, rhs: &Coins) -> Coins {
+        self += rhs;
+        self
+    }
+}
+
+impl Add for Coins {
+    type Output = Coins;
+
+    fn add(self, rhs: Coins) -> Coins {
+        self + &rhs
+    }
+}
+
+impl Add<Coin> for Coins {
+    type Output = Coins;
+
+    fn add(mut self, rhs: Coin) -> Coins {
+        self += rhs;
+        self
+    }
+}
+
+impl AddAssign<&Coins> for Coins {
+    fn add_assign(&mut self, rhs: &Coins) {
+        for (denom, amount) in &rhs.0 {
+            self.checked_add_amount(denom, *amount)
+                .unwrap_or_else(|err| panic!("{}", err));
+        }
+    }
+}
+
+impl AddAssign for Coins {
+    fn add_assign(&mut self, rhs: Coins) {
+        *self += &rhs;
+    }
+}
+
+impl AddAssign<Coin> for Coins {
+    fn add_assign(&mut self, rhs: Coin) {
+        self.checked_add_amount(&rhs.denom, rhs.amount)
+            .unwrap_or_else(|err| panic!("{}", err));
+    }
+}
+
+impl Sub<&Coins> for Coins {
+    type Output = Coins;
+
+    fn sub(mut self, rhs: &Coins) -> Coins {
+        self -= rhs;
+        self
+    }
+}
+
+impl Sub for Coins {
+    type Output = Coins;
+
+    fn sub(self, rhs: Coins) -> Coins {
+        self - &rhs
+    }
+}
+
+impl Sub<Coin> for Coins {
+    type Output = Coins;
+
+    fn sub(mut self, rhs: Coin) -> Coins {
+        self -= rhs;
+        self
+    }
+}
+
+impl SubAssign<&Coins> for Coins {
+    fn sub_assign(&mut self, rhs: &Coins) {
+        for (denom, amount) in &rhs.0 {
+            self.checked_sub_amount(denom, *amount)
+                .unwrap_or_else(|err| panic!("{}", err));
+        }
+    }
+}
+
+impl SubAssign for Coins {
+    fn sub_assign(&mut self, rhs: Coins) {
+        *self -= &rhs;
+    }
+}
+
+impl SubAssign<Coin> for Coins {
+    fn sub_assign(&mut self, rhs: Coin) {
+        self.checked_sub_amount(&rhs.denom, rhs.amount)
+            .unwrap_or_else(|err| panic!("{}", err));
+    }
+}
+
+impl Mul<Uint128> for Coins {
+    type Output = Coins;
+
+    fn mul(self, rhs: Uint128) -> Coins {
+        self.checked_mul(rhs)
+            .unwrap_or_else(|err| panic!("{}", err))
+    }
+}
+
+impl MulAssign<Uint128> for Coins {
+    fn mul_assign(&mut self, rhs: Uint128) {
+        *self = self
+            .checked_mul(rhs)
+            .unwrap_or_else(|err| panic!("{}", err));
+    }
+}
+
+impl Sum<Coin> for Coins {
+    fn sum<I: Iterator<Item = Coin>>(iter: I) -> Coins {
+        iter.fold(Coins::default(), |acc, coin| acc + coin)
+    }
+}
+
+impl<'a> Sum<&'a Coin> for Coins {
+    fn sum<I: Iterator<Item = &'a Coin>>(iter: I) -> Coins {
+        iter.cloned().sum()
+    }
+}
+
+impl Sum for Coins {
+    fn sum<I: Iterator<Item = Coins>>(iter: I) -> Coins {
+        iter.fold(Coins::default(), |acc, coins| acc + &coins)
+    }
+}
+
+impl<'a> Sum<&'a Coins> for Coins {
+    fn sum<I: Iterator<Item = &'a Coins>>(iter: I) -> Coins {
+        iter.fold(Coins::default(), |acc, coins| acc + coins)
+    }
+}
@@ -0,0 +1,163 @@
+use cosmwasm_std::{from_json, to_json_vec, Addr, Order, StdResult, Storage};
+use serde::{Deserialize, Serialize};
+
+use crate::Coins;
+
+/// Migrates a ledger of coin balances in batches, so that arbitrarily large ledgers can be migrated
+/// without running out of gas.
+///
+/// The ledger is expected to be laid out the same way as a cw-storage-plus `Map<&Addr, Coins>`, i.e.
+/// each entry is stored under the length-prefixed namespace followed by the owner address, with
+/// JSON-encoded `Coins` as the value.
+///
+/// Each call to `step` processes at most `limit` entries in ascending order of address, and saves a
+/// cursor under `cursor_key` so the next call (e.g. in a subsequent `sudo` or `migrate` execution)
+/// resumes where the previous one left off. Once all entries have been processed, further calls
+/// are no-ops.
+///
+/// ```rust
+/// use cosmwasm_std::testing::MockStorage;
+/// use cw_coins::LedgerMigration;
+///
+/// const MIGRATION: LedgerMigration = LedgerMigration::new("balances", "balances_migration");
+///
+/// let mut storage = MockStorage::new();
+/// let step = MIGRATION.step(&mut storage, 10, |_, coins| Ok(coins)).unwrap();
+/// assert!(step.done);
+/// ```
+pub struct LedgerMigration<'a> {
+    namespace: &'a str,
+    cursor_key: &'a str,
+}
+
+/// Outcome of a single `LedgerMigration::step` call
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MigrationStep {
+    /// Number of ledger entries processed in this step
+    pub processed: usize,
+    /// Whether all entries in the ledger have now been processed
+    pub done: bool,
+}
+
+#[derive(Serialize, Deserialize)]
+struct Cursor {
+    /// The last processed owner; `None` once the migration is done
+    last: Option<Addr>,
+}
+
+impl<'a> LedgerMigration<'a> {
+    pub const fn new(namespace: &'a str, cursor_key: &'a str) -> Self {
+        Self {
+            namespace,
+            cursor_key,
+        }
+    }
+
+    /// Returns whether all entries in the ledger have been processed.
+    pub fn is_done(&self, storage: &dyn Storage) -> StdResult<bool> {
+        Ok(matches!(
+            self.load_cursor(storage)?,
+            Some(Cursor {
+                last: None
+            })
+        ))
+    }
+
+    /// Applies `transform` to up to `limit` ledger entries following the saved cursor.
+    ///
+    /// If `transform` returns an empty `Coins`, the entry is removed from the ledger. Entries that
+    /// `transform` leaves unchanged are not written back, to save gas.
+    pub fn step<F>(
+        &self,
+        storage: &mut dyn Storage,
+        limit: usize,
+        mut transform: F,
+    ) -> StdResult<MigrationStep>
+    where
+        F: FnMut(&Addr, Coins) -> StdResult<Coins>,
+    {
+        let prefix = self.prefix();
+
+        let start = match self.load_cursor(storage)? {
+            Some(Cursor {
+                last: None,
+            }) => {
+                return Ok(MigrationStep {
+                    processed: 0,
+                    done: true,
+                });
+            }
+            Some(Cursor {
+                last: Some(last),
+            }) => {
+                // start right after the last processed key
+                let mut start = self.key(&prefix, &last);
+                start.push(0);
+                start
+            }
+            None => prefix.clone(),
+        };
+
+        // fetch one more entry than the limit, to find out whether the ledger has been exhausted
+        let entries = storage
+            .range(Some(&start), None, Order::Ascending)
+            .take_while(|(k, _)| k.starts_with(&prefix))
+            .take(limit + 1)
+            .collect::<Vec<_>>();
+        let done = entries.len() <= limit;
+
+        let mut last = None;
+        let mut processed = 0;
+        for (k, v) in entries.into_iter().take(limit) {
+            let owner = Addr::unchecked(String::from_utf8_lossy(&k[prefix.len()..]));
+            let coins: Coins = from_json(&v)?;
+            let migrated = transform(&owner, coins.clone())?;
+            if migrated.is_empty() {
+                storage.remove(&k);
+            } else if migrated != coins {
+                storage.set(&k, &to_json_vec(&migrated)?);
+            }
+            last = Some(owner);
+            processed += 1;
+        }
+
+        // with a limit of zero, there is nothing to update
+        if done || last.is_some() {
+            let cursor = Cursor {
+                last: if done {
+                    None
+                } else {
+                    last
+                },
+            };
+            storage.set(self.cursor_key.as_bytes(), &to_json_vec(&cursor)?);
+        }
+
+        Ok(MigrationStep {
+            processed,
+            done,
+        })
+    }
+
+    fn load_cursor(&self, storage: &dyn Storage) -> StdResult<Option<Cursor>> {
+        storage
+            .get(self.cursor_key.as_bytes())
+            .map(|v| from_json(&v))
+            .transpose()
+    }
+
+    // the same encoding as cw-storage-plus uses for namespaces: a 2-byte big endian length followed
+    // by the namespace itself
+    fn prefix(&self) -> Vec<u8> {
+        let namespace = self.namespace.as_bytes();
+        let mut prefix = (namespace.len() as u16).to_be_bytes().to_vec();
+        prefix.extend_from_slice(namespace);
+        prefix
+    }
+
+    fn key(&self, prefix: &[u8], owner: &Addr) -> Vec<u8> {
+        let mut key = prefix.to_vec();
+        key.extend_from_slice(owner.as_bytes());
+        key
+    }
+}
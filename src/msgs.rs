@@ -0,0 +1,201 @@
+use cosmwasm_std::{BankMsg, Coin, CosmosMsg, WasmMsg};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::{Coins, CoinsError};
+
+impl Coins {
+    /// Aggregates all funds leaving the contract through the given messages.
+    ///
+    /// Accounted for are bank sends and burns, as well as funds attached to wasm execute and
+    /// instantiate messages. Delegations are included if the `staking` feature is enabled, ICS-20
+    /// transfers if the `stargate` feature is enabled, `Instantiate2` with `cosmwasm_1_2` and
+    /// community pool funding with `cosmwasm_1_3`. These features mirror those of cosmwasm-std.
+    ///
+    /// Messages known not to move funds, such as wasm migrations or undelegations, contribute
+    /// nothing. All others are an error, as their funds can't be determined: stargate and custom
+    /// messages, as well as variants that cosmwasm-std has enabled through feature unification but
+    /// the corresponding feature of this crate hasn't.
+    pub fn collect_from_msgs<T>(msgs: &[CosmosMsg<T>]) -> Result<Coins, CoinsError> {
+        collect(msgs, |_| false)
+    }
+}
+
+/// Caps the total amount of funds a handler may send out of the contract.
+///
+/// The wrapped `Coins` is the remaining allowance. Each call to `charge` deducts the outgoing funds
+/// of the given messages from it, and errors if any denom would exceed what is left. Denoms not in
+/// the allowance may not be spent at all. On error, the allowance is left unchanged.
+///
+/// Messages whose funds can't be inspected, such as stargate or custom messages, are rejected, as
+/// they could otherwise be used to move funds past the budget. Use `charge_allowing` to let through
+/// specific ones the caller knows to be harmless.
+///
+/// ```rust
+/// use std::str::FromStr;
+/// use cosmwasm_std::{coin, BankMsg, CosmosMsg, Empty};
+/// use cw_coins::{Coins, SpendBudget};
+///
+/// let mut budget = SpendBudget(Coins::from_str("100uatom").unwrap());
+///
+/// let msgs: Vec<CosmosMsg<Empty>> = vec![CosmosMsg::Bank(BankMsg::Send {
+///     to_address: "alice".to_string(),
+///     amount: vec![coin(60, "uatom")],
+/// })];
+///
+/// assert!(budget.charge(&msgs).is_ok());
+/// assert!(budget.charge(&msgs).is_err());
+/// ```
+#[derive(Serialize, Deserialize, Clone, Default, Debug, PartialEq, JsonSchema)]
+pub struct SpendBudget(pub Coins);
+
+impl SpendBudget {
+    /// Deducts the funds sent out by `msgs` from the remaining allowance.
+    pub fn charge<T>(&mut self, msgs: &[CosmosMsg<T>]) -> Result<(), CoinsError> {
+        self.charge_allowing(msgs, |_| false)
+    }
+
+    /// Same as `charge`, but messages whose funds can't be inspected are let through, and charged
+    /// nothing, if `allow` returns true for them.
+    pub fn charge_allowing<T>(
+        &mut self,
+        msgs: &[CosmosMsg<T>],
+        allow: impl Fn(&CosmosMsg<T>) -> bool,
+    ) -> Result<(), CoinsError> {
+        let spending = collect(msgs, allow)?;
+        self.0 = self.0.checked_sub(&spending)?;
+        Ok(())
+    }
+}
+
+fn collect<T>(
+    msgs: &[CosmosMsg<T>],
+    allow: impl Fn(&CosmosMsg<T>) -> bool,
+) -> Result<Coins, CoinsError> {
+    let mut total = Coins::default();
+    for msg in msgs {
+        let funds = match outgoing_funds(msg) {
+            Err(CoinsError::UninspectableMsg {
+                ..
+            }) if allow(msg) => &[],
+            funds => funds?,
+        };
+        for coin in funds {
+            total.checked_add_amount(&coin.denom, coin.amount)?;
+        }
+    }
+    Ok(total)
+}
+
+fn outgoing_funds<T>(msg: &CosmosMsg<T>) -> Result<&[Coin], CoinsError> {
+    let funds: &[Coin] = match msg {
+        CosmosMsg::Bank(BankMsg::Send {
+            amount,
+            ..
+        }) => amount,
+        CosmosMsg::Bank(BankMsg::Burn {
+            amount,
+        }) => amount,
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            funds,
+            ..
+        }) => funds,
+        CosmosMsg::Wasm(WasmMsg::Instantiate {
+            funds,
+            ..
+        }) => funds,
+        #[cfg(feature = "cosmwasm_1_2")]
+        CosmosMsg::Wasm(WasmMsg::Instantiate2 {
+            funds,
+            ..
+        }) => funds,
+        CosmosMsg::Wasm(
+            WasmMsg::Migrate {
+                ..
+            }
+            | WasmMsg::UpdateAdmin {
+                ..
+            }
+            | WasmMsg::ClearAdmin {
+                ..
+            },
+        ) => &[],
+        #[cfg(feature = "staking")]
+        CosmosMsg::Staking(cosmwasm_std::StakingMsg::Delegate {
+            amount,
+            ..
+        }) => std::slice::from_ref(amount),
+        #[cfg(feature = "staking")]
+        CosmosMsg::Staking(
+            cosmwasm_std::StakingMsg::Undelegate {
+                ..
+            }
+            | cosmwasm_std::StakingMsg::Redelegate {
+                ..
+            },
+        ) => &[],
+        #[cfg(feature = "staking")]
+        CosmosMsg::Distribution(
+            cosmwasm_std::DistributionMsg::SetWithdrawAddress {
+                ..
+            }
+            | cosmwasm_std::DistributionMsg::WithdrawDelegatorReward {
+                ..
+            },
+        ) => &[],
+        #[cfg(all(feature = "staking", feature = "cosmwasm_1_3"))]
+        CosmosMsg::Distribution(cosmwasm_std::DistributionMsg::FundCommunityPool {
+            amount,
+        }) => amount,
+        #[cfg(feature = "stargate")]
+        CosmosMsg::Ibc(cosmwasm_std::IbcMsg::Transfer {
+            amount,
+            ..
+        }) => std::slice::from_ref(amount),
+        #[cfg(feature = "stargate")]
+        CosmosMsg::Ibc(
+            cosmwasm_std::IbcMsg::SendPacket {
+                ..
+            }
+            | cosmwasm_std::IbcMsg::CloseChannel {
+                ..
+            },
+        ) => &[],
+        #[cfg(feature = "stargate")]
+        CosmosMsg::Gov(cosmwasm_std::GovMsg::Vote {
+            ..
+        }) => &[],
+        #[cfg(all(feature = "stargate", feature = "cosmwasm_1_2"))]
+        CosmosMsg::Gov(cosmwasm_std::GovMsg::VoteWeighted {
+            ..
+        }) => &[],
+        _ => {
+            return Err(CoinsError::UninspectableMsg {
+                kind: msg_kind(msg).to_string(),
+            })
+        }
+    };
+    Ok(funds)
+}
+
+fn msg_kind<T>(msg: &CosmosMsg<T>) -> &'static str {
+    match msg {
+        CosmosMsg::Bank(_) => "bank",
+        CosmosMsg::Custom(_) => "custom",
+        CosmosMsg::Wasm(_) => "wasm",
+        #[cfg(feature = "staking")]
+        CosmosMsg::Staking(_) => "staking",
+        #[cfg(feature = "staking")]
+        CosmosMsg::Distribution(_) => "distribution",
+        #[cfg(feature = "stargate")]
+        CosmosMsg::Stargate {
+            ..
+        } => "stargate",
+        #[cfg(feature = "stargate")]
+        CosmosMsg::Ibc(_) => "ibc",
+        #[cfg(feature = "stargate")]
+        CosmosMsg::Gov(_) => "gov",
+        // a variant enabled through another crate's cosmwasm-std features
+        _ => "unsupported",
+    }
+}
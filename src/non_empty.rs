@@ -0,0 +1,65 @@
+use std::ops::Deref;
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::{Coins, CoinsError};
+
+/// Coins that are guaranteed to contain at least one non-zero amount, e.g. for APIs that require a
+/// payment. The guarantee is checked on construction, including when deserializing, so it needn't be
+/// checked again wherever the coins are used.
+///
+/// ```rust
+/// use std::str::FromStr;
+/// use cw_coins::{Coins, NonEmptyCoins};
+///
+/// let coins = NonEmptyCoins::new(Coins::from_str("100uatom").unwrap()).unwrap();
+/// assert_eq!(coins.len(), 1);
+///
+/// assert!(NonEmptyCoins::new(Coins::default()).is_err());
+/// ```
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, Hash, JsonSchema)]
+#[serde(try_from = "Coins")]
+pub struct NonEmptyCoins(Coins);
+
+impl NonEmptyCoins {
+    /// Wraps the coins, erroring if they are empty.
+    pub fn new(coins: Coins) -> Result<Self, CoinsError> {
+        if coins.is_empty() {
+            return Err(CoinsError::Empty);
+        }
+        Ok(Self(coins))
+    }
+
+    pub fn into_inner(self) -> Coins {
+        self.0
+    }
+}
+
+impl Deref for NonEmptyCoins {
+    type Target = Coins;
+
+    fn deref(&self) -> &Coins {
+        &self.0
+    }
+}
+
+impl AsRef<Coins> for NonEmptyCoins {
+    fn as_ref(&self) -> &Coins {
+        &self.0
+    }
+}
+
+impl TryFrom<Coins> for NonEmptyCoins {
+    type Error = CoinsError;
+
+    fn try_from(coins: Coins) -> Result<Self, CoinsError> {
+        Self::new(coins)
+    }
+}
+
+impl From<NonEmptyCoins> for Coins {
+    fn from(coins: NonEmptyCoins) -> Self {
+        coins.0
+    }
+}
@@ -0,0 +1,147 @@
+//! Parsing of untrusted input into `Coins`.
+//!
+//! The `Deserialize` implementation, `FromStr` and the conversions from vectors and iterators all go
+//! through the functions here, so that they agree on which amounts and denoms they accept, and check
+//! them in the same order.
+use std::collections::BTreeMap;
+use std::fmt;
+
+use cosmwasm_std::Uint128;
+use serde::de;
+
+use crate::{invariants, Coins, CoinsError, Denom, ParseOptions};
+
+/// Parses an amount, which must be a non-empty string of ASCII digits. Unlike `Uint128::from_str`,
+/// this refuses a leading `+`, which the SDK doesn't accept either.
+pub(crate) fn parse_amount(s: &str) -> Result<Uint128, CoinsError> {
+    let invalid = || CoinsError::InvalidAmount {
+        amount: s.to_string(),
+    };
+
+    if s.is_empty() || !s.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(invalid());
+    }
+    s.parse::<u128>().map(Uint128::new).map_err(|_| invalid())
+}
+
+/// Removes the `_` separators from a hand-written amount such as `1_000_000`, as long as each of them
+/// sits between two digits. Returns `None` for misplaced separators, as in `_1`, `1_` or `1__0`.
+pub(crate) fn strip_separators(s: &str) -> Option<String> {
+    let bytes = s.as_bytes();
+    let digit_at =
+        |i: Option<usize>| matches!(i.and_then(|i| bytes.get(i)), Some(b) if b.is_ascii_digit());
+    let misplaced = bytes
+        .iter()
+        .enumerate()
+        .any(|(i, b)| *b == b'_' && !(digit_at(i.checked_sub(1)) && digit_at(Some(i + 1))));
+    (!misplaced).then(|| s.replace('_', ""))
+}
+
+/// Parses a single coin string such as `123uatom` or `1_000_000uatom` into its denom and amount.
+/// The denom is not validated here, but by `Collector::push`.
+//
+// `cosmwasm_std::Coin` does not implement `FromStr`, so we have do it ourselves
+//
+// Parsing the string with regex doesn't work, because the resulting wasm binary would be too big
+// from including the `regex` library.
+//
+// If the binary size is not a concern, here's an example:
+// https://github.com/PFC-Validator/terra-rust/blob/v1.1.8/terra-rust-api/src/client/core_types.rs#L34-L55
+//
+// We opt for the following solution: split the string before the first character that is not an
+// ASCII digit or underscore. Everything before it must be a non-empty amount, and everything after
+// it the denom. As a denom must start with a letter, this splits every well-formed coin string the
+// same way the SDK's regex does:
+// https://github.com/cosmos/cosmos-sdk/blob/v0.46.0/types/coin.go#L854-L856
+pub(crate) fn parse_coin_str(s: &str) -> Result<(String, Uint128), CoinsError> {
+    let invalid = || CoinsError::Parse {
+        input: s.to_string(),
+    };
+
+    let i = s
+        .find(|c: char| !c.is_ascii_digit() && c != '_')
+        .ok_or_else(invalid)?;
+    if i == 0 {
+        return Err(invalid());
+    }
+
+    let amount = strip_separators(&s[..i]).ok_or_else(|| CoinsError::InvalidAmount {
+        amount: s[..i].to_string(),
+    })?;
+    Ok((s[i..].to_string(), parse_amount(&amount)?))
+}
+
+/// A string deserialized with `deserialize_str` rather than `deserialize_string`, as `String` does.
+/// Versions of `serde-json-wasm` before 0.5 only support the former for map keys, and every other
+/// deserializer accepts it as well.
+pub(crate) struct Key(pub String);
+
+impl<'de> de::Deserialize<'de> for Key {
+    fn deserialize<D: de::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct Visitor;
+
+        impl<'de> de::Visitor<'de> for Visitor {
+            type Value = Key;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("a string")
+            }
+
+            fn visit_str<E: de::Error>(self, s: &str) -> Result<Key, E> {
+                Ok(Key(s.to_string()))
+            }
+
+            fn visit_string<E: de::Error>(self, s: String) -> Result<Key, E> {
+                Ok(Key(s))
+            }
+        }
+
+        deserializer.deserialize_str(Visitor)
+    }
+}
+
+/// Collects parsed entries into `Coins` according to the given options.
+pub(crate) struct Collector<'a> {
+    options: &'a ParseOptions,
+    map: BTreeMap<Denom, Uint128>,
+}
+
+impl<'a> Collector<'a> {
+    pub fn new(options: &'a ParseOptions) -> Self {
+        Self {
+            options,
+            map: BTreeMap::new(),
+        }
+    }
+
+    /// Adds an entry, erroring if it is one too many, a duplicate, has a blank or invalid denom, or
+    /// is a zero amount that the options reject. Zero amounts that are accepted are still
+    /// remembered, to count towards duplicates and the maximum.
+    pub fn push(&mut self, denom: String, amount: Uint128) -> Result<(), CoinsError> {
+        let denom = self.options.normalize(denom);
+        self.options.check_count(self.map.len() + 1)?;
+        if self.map.contains_key(denom.as_str()) {
+            return Err(CoinsError::DuplicateDenom {
+                denom,
+            });
+        }
+        let denom = Denom::new(denom)?;
+        self.options.denoms.check(&denom)?;
+        self.options.zeros.admit(&denom, amount)?;
+        self.map.insert(denom, amount);
+        Ok(())
+    }
+
+    /// Same as `push`, but parsing the amount first.
+    pub fn push_str(&mut self, denom: String, amount: &str) -> Result<(), CoinsError> {
+        self.push(denom, parse_amount(amount)?)
+    }
+
+    /// Leaves out zero amounts, erroring if nothing is left and empty coins are not allowed.
+    pub fn finish(self) -> Result<Coins, CoinsError> {
+        let coins = Coins::from_map_unchecked(self.map);
+        invariants::check("parse", &coins);
+        self.options.check_empty(&coins)?;
+        Ok(coins)
+    }
+}
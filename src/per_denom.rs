@@ -0,0 +1,128 @@
+use std::collections::{btree_map, BTreeMap};
+use std::fmt;
+use std::marker::PhantomData;
+
+use schemars::JsonSchema;
+use serde::{de, Serialize};
+
+use crate::denom::validate_denom;
+use crate::parse::Key;
+use crate::{CoinsError, Denom};
+
+/// A map from denoms to per-denom configuration values of an arbitrary type, e.g. caps, collateral
+/// factors or fee rates.
+///
+/// Keys follow the same semantics as those of [`Coins`](crate::Coins): they are sorted, and
+/// duplicates are rejected during deserialization. Additionally, every key must be a valid Cosmos SDK
+/// denom, both when deserializing and when inserting.
+#[derive(Serialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PerDenom<T>(BTreeMap<Denom, T>);
+
+// Derive would require `T: Default`, which the map itself doesn't need
+impl<T> Default for PerDenom<T> {
+    fn default() -> Self {
+        Self(BTreeMap::new())
+    }
+}
+
+// Same as for `Coins`, we implement a custom deserializer to reject duplicate keys rather than
+// silently taking the one seen the last. See the comments there for details.
+impl<'de, T> de::Deserialize<'de> for PerDenom<T>
+where
+    T: de::Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        struct Visitor<T>(PhantomData<T>);
+
+        impl<'de, T> de::Visitor<'de> for Visitor<T>
+        where
+            T: de::Deserialize<'de>,
+        {
+            type Value = PerDenom<T>;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("a map with non-duplicating, valid denoms as keys")
+            }
+
+            #[inline]
+            fn visit_map<M>(self, mut access: M) -> Result<Self::Value, M::Error>
+            where
+                M: de::MapAccess<'de>,
+            {
+                let parse_err =
+                    |err: &dyn fmt::Display| format!("failed to parse into PerDenom! {}", err);
+                let mut map = BTreeMap::<Denom, T>::new();
+
+                while let Some(Key(denom)) = access.next_key::<Key>()? {
+                    if map.contains_key(denom.as_str()) {
+                        return Err(de::Error::custom(parse_err(&CoinsError::DuplicateDenom {
+                            denom,
+                        })));
+                    }
+
+                    validate_denom(&denom).map_err(|err| de::Error::custom(parse_err(&err)))?;
+                    let denom =
+                        Denom::new(denom).map_err(|err| de::Error::custom(parse_err(&err)))?;
+
+                    let value = access.next_value::<T>()?;
+                    map.insert(denom, value);
+                }
+
+                Ok(PerDenom(map))
+            }
+        }
+
+        deserializer.deserialize_any(Visitor(PhantomData))
+    }
+}
+
+impl<T> PerDenom<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Wraps a map taken as it is, for results keyed by the denoms of an existing `Coins`.
+    pub(crate) fn from_map_unchecked(map: BTreeMap<Denom, T>) -> Self {
+        Self(map)
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Sets the value for a denom after validating it, returning the previous value if any.
+    pub fn insert(&mut self, denom: impl Into<String>, value: T) -> Result<Option<T>, CoinsError> {
+        let denom = denom.into();
+        validate_denom(&denom)?;
+        Ok(self.0.insert(Denom::new(denom)?, value))
+    }
+
+    /// Iterates over the denoms and their values, in ascending order of denom.
+    pub fn iter(&self) -> btree_map::Iter<'_, Denom, T> {
+        self.0.iter()
+    }
+
+    pub fn get(&self, denom: &str) -> Option<&T> {
+        self.0.get(denom)
+    }
+
+    /// Returns the value for a denom, or the given default if the denom is not configured.
+    pub fn get_or<'a>(&'a self, denom: &str, default: &'a T) -> &'a T {
+        self.0.get(denom).unwrap_or(default)
+    }
+
+    /// Returns a copy of the value for a denom, or `T::default()` if the denom is not configured.
+    pub fn get_or_default(&self, denom: &str) -> T
+    where
+        T: Clone + Default,
+    {
+        self.0.get(denom).cloned().unwrap_or_default()
+    }
+}
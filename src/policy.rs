@@ -0,0 +1,141 @@
+use cosmwasm_std::Uint128;
+
+use crate::{denom, Coins, CoinsError};
+
+/// How a constructor treats zero-amount coins in its input.
+///
+/// `Coins` never holds zero amounts, so there is no policy to keep them: the choice is only between
+/// silently leaving them out, which all constructors do by default, and refusing the input, e.g.
+/// for messages whose senders are expected to know better.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ZeroPolicy {
+    /// Zero-amount coins are left out
+    #[default]
+    Strip,
+    /// Zero-amount coins are an error
+    Reject,
+}
+
+impl ZeroPolicy {
+    /// Returns whether a coin of the given denom and amount is to be stored, or an error if it must
+    /// be refused.
+    pub(crate) fn admit(self, denom: &str, amount: Uint128) -> Result<bool, CoinsError> {
+        match self {
+            _ if !amount.is_zero() => Ok(true),
+            ZeroPolicy::Strip => Ok(false),
+            ZeroPolicy::Reject => Err(CoinsError::ZeroAmount {
+                denom: denom.to_string(),
+            }),
+        }
+    }
+}
+
+/// How thoroughly a constructor checks the denoms in its input.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum DenomValidation {
+    /// Denoms are taken as they are, e.g. for data read from storage or coins coming from the bank
+    /// module, which have been validated before
+    #[default]
+    Unchecked,
+    /// Denoms are checked against the Cosmos SDK's rules with `validate_denom`
+    Sdk,
+    /// Denoms are checked with `validate_denom_strict`, which additionally checks the format of IBC
+    /// and tokenfactory denoms
+    Strict,
+}
+
+impl DenomValidation {
+    pub(crate) fn check(self, denom: &str) -> Result<(), CoinsError> {
+        match self {
+            DenomValidation::Unchecked => Ok(()),
+            DenomValidation::Sdk => denom::validate_denom(denom),
+            DenomValidation::Strict => denom::validate_denom_strict(denom),
+        }
+    }
+}
+
+/// Options for parsing coins from untrusted input, such as messages, queried data or plain text
+/// strings. The defaults are what the `Deserialize` implementation and `TryFrom<Vec<Coin>>` use.
+///
+/// ```rust
+/// use cw_coins::{Coins, DenomValidation, ParseOptions, ZeroPolicy};
+///
+/// let options = ParseOptions {
+///     zeros: ZeroPolicy::Reject,
+///     denoms: DenomValidation::Sdk,
+///     max_denoms: 2,
+///     ..Default::default()
+/// };
+///
+/// let mut de = serde_json::Deserializer::from_str(r#"{"uatom":"1","umars":"2","uosmo":"3"}"#);
+/// assert!(Coins::deserialize_with_options(&mut de, &options).is_err());
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ParseOptions {
+    /// How zero amounts are treated
+    pub zeros: ZeroPolicy,
+    /// How thoroughly denoms are checked
+    pub denoms: DenomValidation,
+    /// Whether to upper-case the hash of IBC denoms with `normalize_ibc_denom`, before checking for
+    /// duplicates and validating them
+    pub normalize_ibc: bool,
+    /// Whether input resulting in empty coins is accepted, including input consisting of nothing
+    /// but zero amounts. If not, `CoinsError::Empty` is returned.
+    pub allow_empty: bool,
+    /// The maximum number of entries accepted, counting zero amounts. Enforced while parsing, so an
+    /// absurdly large input is refused before much gas is spent on it.
+    pub max_denoms: usize,
+}
+
+impl ParseOptions {
+    pub const DEFAULT_MAX_DENOMS: usize = 256;
+
+    /// The options the `Deserialize` implementation of `Coins` uses, meant for data loaded from
+    /// storage: the defaults, but without a maximum number of entries, since coins that grew past it
+    /// through addition must still load.
+    pub fn storage() -> Self {
+        Self {
+            max_denoms: usize::MAX,
+            ..Self::default()
+        }
+    }
+
+    /// Normalizes a denom taken in, if enabled.
+    pub(crate) fn normalize(&self, denom: String) -> String {
+        if self.normalize_ibc {
+            denom::normalize_ibc_denom(denom)
+        } else {
+            denom
+        }
+    }
+
+    /// Errors if the resulting coins are empty and that is not allowed.
+    pub(crate) fn check_empty(&self, coins: &Coins) -> Result<(), CoinsError> {
+        if coins.is_empty() && !self.allow_empty {
+            return Err(CoinsError::Empty);
+        }
+        Ok(())
+    }
+
+    /// Errors if `count` entries exceed the maximum.
+    pub(crate) fn check_count(&self, count: usize) -> Result<(), CoinsError> {
+        if count > self.max_denoms {
+            return Err(CoinsError::TooManyDenoms {
+                max: self.max_denoms,
+            });
+        }
+        Ok(())
+    }
+}
+
+impl Default for ParseOptions {
+    fn default() -> Self {
+        Self {
+            zeros: ZeroPolicy::default(),
+            denoms: DenomValidation::default(),
+            normalize_ibc: false,
+            allow_empty: true,
+            max_denoms: Self::DEFAULT_MAX_DENOMS,
+        }
+    }
+}
@@ -0,0 +1,211 @@
+use std::collections::BTreeMap;
+
+use cosmwasm_std::{Addr, Uint128};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::{shadow, Coins, CoinsError};
+
+/// A multi-asset pool that tracks the total deposited coins as well as how many share units each
+/// owner holds.
+///
+/// The first deposit into an empty pool mints as many shares as the largest amount among the
+/// deposited coins. Subsequent deposits must contain every denom currently in the pool, and mint
+/// shares pro-rata to the _least_ represented denom, rounding down. The whole deposit is added to the
+/// pool, so any amount in excess of the pool's current ratio accrues to all share holders.
+///
+/// Withdrawals pay out each denom pro-rata to the number of shares burned, rounding down. When the
+/// last outstanding shares are withdrawn, all remaining coins (including rounding dust) are paid
+/// out, so the pool never strands funds.
+///
+/// Each withdrawal is a plain `multiply_ratio` rather than a run of an allocation solver such as
+/// `split_weighted`. A solver hands leftover dust to some of the parts, which only conserves the
+/// total when every part is paid out at once. A withdrawal pays a single owner, so any dust it
+/// received would belong to the other owners, and repeated small withdrawals could drain them
+/// unit by unit. Rounding down instead leaves the dust in the pool, to the benefit of the
+/// remaining shares, and the accounting stays exact: the pool's total always equals everything
+/// deposited minus everything paid out.
+///
+/// Owners whose share balance drops to zero are removed from the pool.
+#[derive(Serialize, Deserialize, Clone, Default, Debug, PartialEq, JsonSchema)]
+pub struct PooledCoins {
+    total: Coins,
+    total_shares: Uint128,
+    #[serde(with = "owner_map")]
+    #[schemars(with = "BTreeMap<String, Uint128>")]
+    shares: BTreeMap<Addr, Uint128>,
+}
+
+impl PooledCoins {
+    /// Returns the total coins held by the pool.
+    pub fn total(&self) -> &Coins {
+        &self.total
+    }
+
+    /// Returns the total share units outstanding.
+    pub fn total_shares(&self) -> Uint128 {
+        self.total_shares
+    }
+
+    /// Returns the share units held by each owner.
+    pub fn shares(&self) -> &BTreeMap<Addr, Uint128> {
+        &self.shares
+    }
+
+    /// Returns the number of share units held by the given owner.
+    pub fn shares_of(&self, owner: &Addr) -> Uint128 {
+        self.shares.get(owner).copied().unwrap_or_default()
+    }
+
+    /// Deposits coins into the pool on behalf of `owner`, returning the number of shares minted.
+    pub fn deposit(&mut self, owner: &Addr, coins: &Coins) -> Result<Uint128, CoinsError> {
+        let minted = if self.total_shares.is_zero() {
+            coins.0.values().max().copied().unwrap_or_default()
+        } else {
+            let mut minted: Option<Uint128> = None;
+            for (denom, total_amount) in &self.total.0 {
+                let amount = coins.0.get(denom).copied().unwrap_or_default();
+                let shares = amount.checked_multiply_ratio(self.total_shares, *total_amount);
+                shadow::verify_mul_ratio(
+                    denom,
+                    amount,
+                    self.total_shares,
+                    *total_amount,
+                    shares.as_ref().ok().copied(),
+                );
+                let shares = shares.map_err(|_| CoinsError::ShareOverflow {
+                    denom: denom.to_string(),
+                })?;
+                minted = Some(minted.map_or(shares, |m| m.min(shares)));
+            }
+            minted.unwrap_or_default()
+        };
+
+        if minted.is_zero() {
+            return Err(CoinsError::DepositTooSmall);
+        }
+
+        let mut total = self.total.clone();
+        for (denom, amount) in &coins.0 {
+            total.checked_add_amount(denom, *amount)?;
+        }
+
+        let mint_overflow = |current| CoinsError::MintOverflow {
+            current,
+            minted,
+        };
+        let total_shares = self
+            .total_shares
+            .checked_add(minted)
+            .map_err(|_| mint_overflow(self.total_shares))?;
+        let owner_shares = self.shares_of(owner);
+        let owner_shares = owner_shares
+            .checked_add(minted)
+            .map_err(|_| mint_overflow(owner_shares))?;
+
+        self.total = total;
+        self.total_shares = total_shares;
+        self.shares.insert(owner.clone(), owner_shares);
+
+        Ok(minted)
+    }
+
+    /// Burns `shares` units held by `owner`, returning the coins they are redeemed for.
+    pub fn withdraw(&mut self, owner: &Addr, shares: Uint128) -> Result<Coins, CoinsError> {
+        let owner_shares = self.shares_of(owner);
+        if shares > owner_shares {
+            return Err(CoinsError::InsufficientShares {
+                owner: owner.to_string(),
+                needed: shares,
+                available: owner_shares,
+            });
+        }
+
+        let payout = if shares == self.total_shares {
+            std::mem::take(&mut self.total)
+        } else {
+            let payout = self
+                .total
+                .0
+                .iter()
+                .map(|(denom, amount)| {
+                    let payout = amount.multiply_ratio(shares, self.total_shares);
+                    shadow::verify_mul_ratio(
+                        denom,
+                        *amount,
+                        shares,
+                        self.total_shares,
+                        Some(payout),
+                    );
+                    (denom.clone(), payout)
+                })
+                .filter(|(_, amount)| !amount.is_zero())
+                .collect::<BTreeMap<_, _>>();
+
+            for (denom, amount) in &payout {
+                if let Some(total_amount) = self.total.0.get_mut(denom) {
+                    *total_amount -= *amount;
+                }
+            }
+            self.total.0.retain(|_, amount| !amount.is_zero());
+
+            Coins::from_map_unchecked(payout)
+        };
+
+        self.total_shares -= shares;
+        if shares == owner_shares {
+            self.shares.remove(owner);
+        } else {
+            self.shares.insert(owner.clone(), owner_shares - shares);
+        }
+
+        Ok(payout)
+    }
+}
+
+/// (De)serializes the share map with plain string keys. `serde-json-wasm` treats `Addr` keys as
+/// newtype structs, which it doesn't support in key position.
+mod owner_map {
+    use std::collections::BTreeMap;
+    use std::fmt;
+
+    use cosmwasm_std::{Addr, Uint128};
+    use serde::{de, Deserializer, Serializer};
+
+    use crate::parse::Key;
+
+    pub fn serialize<S: Serializer>(
+        shares: &BTreeMap<Addr, Uint128>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.collect_map(
+            shares
+                .iter()
+                .map(|(owner, shares)| (owner.as_str(), shares)),
+        )
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<BTreeMap<Addr, Uint128>, D::Error> {
+        struct Visitor;
+
+        impl<'de> de::Visitor<'de> for Visitor {
+            type Value = BTreeMap<Addr, Uint128>;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("a map of owners to share units")
+            }
+
+            fn visit_map<A: de::MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+                let mut shares = BTreeMap::new();
+                while let Some((Key(owner), amount)) = map.next_entry::<Key, Uint128>()? {
+                    shares.insert(Addr::unchecked(owner), amount);
+                }
+                Ok(shares)
+            }
+        }
+
+        deserializer.deserialize_map(Visitor)
+    }
+}
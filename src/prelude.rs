@@ -0,0 +1,42 @@
+//! Convenience re-exports of the most commonly used types, along with extension traits for
+//! converting cosmwasm-std types into [`Coins`]:
+//!
+//! ```rust
+//! use cosmwasm_std::coin;
+//! use cosmwasm_std::testing::mock_info;
+//! use cw_coins::prelude::*;
+//!
+//! let info = mock_info("sender", &[coin(100, "uatom")]);
+//! let coins: Coins = info.to_coins().unwrap();
+//! assert_eq!(coins.to_string(), "100uatom");
+//! ```
+use cosmwasm_std::{Coin, MessageInfo};
+
+pub use crate::{
+    Coins, CoinsError, Denom, FetchErrors, InsufficientFunds, NonEmptyCoins, Shortfall,
+};
+
+/// Conversion of coin lists into `Coins`, erroring on duplicate denoms the same way as
+/// `TryFrom<Vec<Coin>>` does.
+pub trait ToCoins {
+    fn to_coins(&self) -> Result<Coins, CoinsError>;
+}
+
+impl ToCoins for [Coin] {
+    fn to_coins(&self) -> Result<Coins, CoinsError> {
+        Coins::try_from(self)
+    }
+}
+
+impl ToCoins for Vec<Coin> {
+    fn to_coins(&self) -> Result<Coins, CoinsError> {
+        Coins::try_from(self.as_slice())
+    }
+}
+
+/// Converts the funds sent along with a message.
+impl ToCoins for MessageInfo {
+    fn to_coins(&self) -> Result<Coins, CoinsError> {
+        self.funds.to_coins()
+    }
+}
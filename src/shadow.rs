@@ -0,0 +1,132 @@
+//! Shadow computation of Coins arithmetic in 256 bits.
+//!
+//! With the `shadow-math` feature enabled in a debug build, every arithmetic operation on amounts is
+//! recomputed with `Uint256` operands, and the two results compared. A divergence means the 128-bit
+//! code path silently truncated, wrapped, or reported a spurious overflow, and panics. Operations
+//! whose code path already computes in 256 bits, such as `mul_floor` or `split_weighted`, are
+//! recomputed in 512 bits instead.
+//!
+//! Without the feature, or in release builds (including wasm), the checks compile to nothing.
+use cosmwasm_std::{Decimal, Uint128, Uint256, Uint512};
+
+/// Verifies the `result` of operation `op` on `denom` against `wide`, the same operation computed
+/// in 256 bits. Either side being `None` denotes an overflow or underflow.
+#[inline(always)]
+pub(crate) fn verify<F>(op: &str, denom: &str, result: Option<Uint128>, wide: F)
+where
+    F: FnOnce() -> Option<Uint256>,
+{
+    #[cfg(all(feature = "shadow-math", debug_assertions))]
+    {
+        let expected = wide().and_then(|amount| Uint128::try_from(amount).ok());
+        assert_eq!(result, expected, "shadow computation of `{}` diverged for {}", op, denom);
+    }
+
+    #[cfg(not(all(feature = "shadow-math", debug_assertions)))]
+    let _ = (op, denom, result, wide);
+}
+
+#[inline(always)]
+pub(crate) fn verify_add(denom: &str, a: Uint128, b: Uint128, result: Option<Uint128>) {
+    verify("add", denom, result, || Some(Uint256::from(a) + Uint256::from(b)))
+}
+
+#[inline(always)]
+pub(crate) fn verify_sub(denom: &str, a: Uint128, b: Uint128, result: Option<Uint128>) {
+    verify("sub", denom, result, || Uint256::from(a).checked_sub(Uint256::from(b)).ok())
+}
+
+#[inline(always)]
+pub(crate) fn verify_mul(denom: &str, a: Uint128, b: Uint128, result: Option<Uint128>) {
+    verify("mul", denom, result, || Some(Uint256::from(a) * Uint256::from(b)))
+}
+
+#[inline(always)]
+pub(crate) fn verify_mul_ratio(
+    denom: &str,
+    a: Uint128,
+    numerator: Uint128,
+    denominator: Uint128,
+    result: Option<Uint128>,
+) {
+    verify("mul_ratio", denom, result, || {
+        (Uint256::from(a) * Uint256::from(numerator))
+            .checked_div(Uint256::from(denominator))
+            .ok()
+    })
+}
+
+#[inline(always)]
+pub(crate) fn verify_saturating_sub(denom: &str, a: Uint128, b: Uint128, result: Uint128) {
+    verify("saturating_sub", denom, Some(result), || {
+        Some(Uint256::from(a).saturating_sub(Uint256::from(b)))
+    })
+}
+
+#[inline(always)]
+pub(crate) fn verify_abs_diff(denom: &str, a: Uint128, b: Uint128, result: Uint128) {
+    verify("abs_diff", denom, Some(result), || {
+        let (a, b) = (Uint256::from(a), Uint256::from(b));
+        Some(if a > b {
+            a - b
+        } else {
+            b - a
+        })
+    })
+}
+
+#[inline(always)]
+pub(crate) fn verify_div(
+    denom: &str,
+    a: Uint128,
+    divisor: Uint128,
+    quotient: Uint128,
+    remainder: Uint128,
+) {
+    verify("div", denom, Some(quotient), || {
+        Uint256::from(a).checked_div(Uint256::from(divisor)).ok()
+    });
+    verify("rem", denom, Some(remainder), || {
+        Uint256::from(a).checked_rem(Uint256::from(divisor)).ok()
+    });
+}
+
+/// Verifies `a * numerator / denominator`, rounded up if `round_up` is set, in 512 bits. Like the
+/// code paths it checks, it treats a product exceeding 256 bits as an overflow.
+#[inline(always)]
+pub(crate) fn verify_mul_wide(
+    op: &str,
+    denom: &str,
+    a: Uint128,
+    numerator: Uint256,
+    denominator: Uint256,
+    round_up: bool,
+    result: Option<Uint128>,
+) {
+    verify(op, denom, result, || {
+        let product = Uint512::from(a) * Uint512::from(numerator);
+        if product > Uint512::from(Uint256::MAX) {
+            return None;
+        }
+        let denominator = Uint512::from(denominator);
+        let mut quotient = product.checked_div(denominator).ok()?;
+        if round_up && !(product % denominator).is_zero() {
+            quotient += Uint512::one();
+        }
+        Uint256::try_from(quotient).ok()
+    })
+}
+
+#[inline(always)]
+pub(crate) fn verify_lerp(denom: &str, from: Uint128, to: Uint128, t: Decimal, result: Uint128) {
+    verify("lerp", denom, Some(result), || {
+        let unit = Uint256::from(Decimal::one().atomics());
+        let t = Uint256::from(t.atomics());
+        let (from, to) = (Uint256::from(from), Uint256::from(to));
+        Some(if to >= from {
+            from + (to - from) * t / unit
+        } else {
+            from - (from - to) * t / unit
+        })
+    })
+}
@@ -0,0 +1,20 @@
+use std::str::FromStr;
+
+use cw_coins::{AliasMap, Coins};
+
+#[test]
+fn consolidating() {
+    let mut aliases = AliasMap::new();
+    aliases.insert("ibc/ABCD", "uatom".to_string()).unwrap();
+    aliases.insert("ibc/1234", "uatom".to_string()).unwrap();
+    aliases.insert("ibc/EF56", "uosmo".to_string()).unwrap();
+
+    let coins = Coins::from_str("1ibc/1234,20ibc/ABCD,300uatom,4umars,5ibc/EF56").unwrap();
+    assert_eq!(coins.consolidate(&aliases).unwrap().to_string(), "321uatom,4umars,5uosmo");
+
+    // nothing to consolidate
+    assert_eq!(coins.consolidate(&AliasMap::new()).unwrap(), coins);
+
+    let coins = Coins::from_str(&format!("{}ibc/ABCD,1uatom", u128::MAX)).unwrap();
+    assert!(coins.consolidate(&aliases).is_err());
+}
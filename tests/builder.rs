@@ -0,0 +1,74 @@
+use cosmwasm_std::coin;
+use cw_coins::{Coins, CoinsError};
+
+#[test]
+fn building() {
+    let coins = Coins::builder()
+        .add("uatom", 12345u128)
+        .add("uosmo".to_string(), 500u64)
+        .add_coin(coin(1, "umars"))
+        .build()
+        .unwrap();
+    assert_eq!(coins.to_string(), "12345uatom,1umars,500uosmo");
+
+    assert!(Coins::builder().build().unwrap().is_empty());
+}
+
+#[test]
+fn building_invalid_coins() {
+    // every problem is reported, in the order the coins were added
+    let err = Coins::builder()
+        .add("uosmo", 1u128)
+        .add("uatom", 0u128)
+        .add("uosmo", 2u128)
+        .add("ab", 3u128)
+        .add("uosmo", 0u128)
+        .build()
+        .unwrap_err();
+    assert_eq!(
+        err.errors,
+        vec![
+            CoinsError::ZeroAmount {
+                denom: "uatom".to_string(),
+            },
+            CoinsError::DuplicateDenom {
+                denom: "uosmo".to_string(),
+            },
+            CoinsError::InvalidDenom {
+                denom: "ab".to_string(),
+                reason: "length must be between 3 and 128 characters".to_string(),
+            },
+            CoinsError::DuplicateDenom {
+                denom: "uosmo".to_string(),
+            },
+            CoinsError::ZeroAmount {
+                denom: "uosmo".to_string(),
+            },
+        ]
+    );
+
+    // a blank denom is reported as invalid rather than skipped
+    let err = Coins::builder().add(" ", 1u128).build().unwrap_err();
+    assert!(err.to_string().contains("invalid denom ` `"));
+
+    // a single problem fails the whole build
+    Coins::builder()
+        .add("uatom", 1u128)
+        .add_coin(coin(0, "uosmo"))
+        .build()
+        .unwrap_err();
+}
+
+#[test]
+fn checking_untrusted_coins() {
+    let funds = vec![coin(12345, "uatom"), coin(500, "uosmo")];
+    assert_eq!(Coins::new_checked(funds).unwrap().to_string(), "12345uatom,500uosmo");
+
+    let funds = vec![coin(0, "uatom"), coin(500, "uosmo"), coin(1, "uosmo"), coin(1, "u$d")];
+    let err = Coins::new_checked(funds).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "invalid coins: zero amount for uatom; duplicate denom: uosmo; invalid denom \
+        `u$d`: contains characters other than letters, numbers or `/:._-`"
+    );
+}
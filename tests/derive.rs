@@ -0,0 +1,104 @@
+#![cfg(feature = "derive")]
+
+use std::str::FromStr;
+
+use cw_coins::{Coins, ValidateCoins};
+
+#[derive(ValidateCoins)]
+struct DepositMsg {
+    #[coins(validate, max_denoms = 2)]
+    funds: Coins,
+    #[coins(max_denoms = 1)]
+    fee: Coins,
+    // not annotated, so not checked
+    memo: Coins,
+}
+
+#[derive(ValidateCoins)]
+enum ExecuteMsg {
+    Deposit {
+        #[coins(validate)]
+        funds: Coins,
+    },
+    Refund(#[coins(max_denoms = 1)] Coins),
+    Transfer(String, #[coins(validate)] Coins),
+    Claim {},
+}
+
+fn coins(s: &str) -> Coins {
+    Coins::from_str(s).unwrap()
+}
+
+/// Builds coins with a denom that `from_str` would reject
+fn invalid_coins(denom: &str, amount: u128) -> Coins {
+    serde_json::from_str(&format!(r#"{{"{}":"{}"}}"#, denom, amount)).unwrap()
+}
+
+#[test]
+fn validating_structs() {
+    let msg = DepositMsg {
+        funds: coins("100uatom,50uosmo"),
+        fee: coins("1uosmo"),
+        memo: invalid_coins("u", 1),
+    };
+    msg.validate().unwrap();
+    assert_eq!(msg.memo.len(), 1);
+
+    let msg = DepositMsg {
+        funds: coins("100uatom,50uosmo,1umars"),
+        fee: coins("1uosmo"),
+        memo: Coins::default(),
+    };
+    let err = msg.validate().unwrap_err();
+    assert!(err
+        .to_string()
+        .contains("`funds` contains 3 denoms, more than the maximum of 2"));
+
+    let msg = DepositMsg {
+        funds: invalid_coins("ua", 100) + coins("1uatom"),
+        fee: Coins::default(),
+        memo: Coins::default(),
+    };
+    let err = msg.validate().unwrap_err();
+    assert!(err.to_string().contains("`funds`: invalid denom `ua`"));
+
+    // fields without `validate` are only checked for their length
+    let msg = DepositMsg {
+        funds: Coins::default(),
+        fee: invalid_coins("ua", 1),
+        memo: Coins::default(),
+    };
+    msg.validate().unwrap();
+}
+
+#[test]
+fn validating_enums() {
+    ExecuteMsg::Deposit {
+        funds: coins("100uatom"),
+    }
+    .validate()
+    .unwrap();
+
+    ExecuteMsg::Deposit {
+        funds: invalid_coins("1uatom", 100),
+    }
+    .validate()
+    .unwrap_err();
+
+    ExecuteMsg::Refund(coins("1uatom")).validate().unwrap();
+    let err = ExecuteMsg::Refund(coins("1uatom,2uosmo"))
+        .validate()
+        .unwrap_err();
+    assert!(err.to_string().contains("`0` contains 2 denoms"));
+
+    // unannotated positional fields are skipped
+    let msg = ExecuteMsg::Transfer("alice".to_string(), coins("1uatom"));
+    msg.validate().unwrap();
+    assert!(matches!(msg, ExecuteMsg::Transfer(recipient, _) if recipient == "alice"));
+    let err = ExecuteMsg::Transfer("alice".to_string(), invalid_coins("ua", 1))
+        .validate()
+        .unwrap_err();
+    assert!(err.to_string().contains("`1`: invalid denom `ua`"));
+
+    ExecuteMsg::Claim {}.validate().unwrap();
+}
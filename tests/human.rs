@@ -0,0 +1,144 @@
+use std::str::FromStr;
+
+use cw_coins::{Coins, CoinsError, DenomMetadata, PerDenom};
+
+fn metadata() -> Vec<DenomMetadata> {
+    vec![
+        DenomMetadata {
+            denom: "uatom".to_string(),
+            symbol: "ATOM".to_string(),
+            decimals: 6,
+        },
+        DenomMetadata {
+            denom: "uosmo".to_string(),
+            symbol: "OSMO".to_string(),
+            decimals: 6,
+        },
+        DenomMetadata {
+            denom: "ibc/1234ABCD".to_string(),
+            symbol: "NOFRAC".to_string(),
+            decimals: 0,
+        },
+    ]
+}
+
+#[test]
+fn parsing_human_strings() {
+    let coins = Coins::from_human_str("12.5 ATOM, 3 OSMO,  7 NOFRAC", &metadata()).unwrap();
+    assert_eq!(coins, Coins::from_str("12500000uatom,3000000uosmo,7ibc/1234ABCD").unwrap());
+
+    let coins = Coins::from_human_str("0.000001 ATOM", &metadata()).unwrap();
+    assert_eq!(coins, Coins::from_str("1uatom").unwrap());
+
+    // more decimals than the asset supports
+    let err = Coins::from_human_str("0.0000001 ATOM", &metadata()).unwrap_err();
+    assert!(err.to_string().contains("more than 6 decimal places"));
+
+    // unknown symbol
+    let err = Coins::from_human_str("1 MARS", &metadata()).unwrap_err();
+    assert_eq!(
+        err,
+        CoinsError::UnknownSymbol {
+            symbol: "MARS".to_string(),
+        }
+    );
+
+    // malformed inputs
+    for s in ["1", "1 ATOM OSMO", "1.2.3 ATOM", ".5 ATOM", "-1 ATOM", "1e6 ATOM"] {
+        Coins::from_human_str(s, &metadata()).unwrap_err();
+    }
+
+    // duplicates
+    let err = Coins::from_human_str("1 ATOM, 2 ATOM", &metadata()).unwrap_err();
+    assert!(err.to_string().contains("duplicate denom: uatom"));
+}
+
+#[test]
+fn formatting_human_strings() {
+    let coins = Coins::from_str("12500000uatom,3000000uosmo,7ibc/1234ABCD,1umars").unwrap();
+    let s = coins.to_human_string(&metadata()).unwrap();
+    assert_eq!(s, "7 NOFRAC, 12.5 ATOM, 1 umars, 3 OSMO");
+
+    // denoms without metadata don't parse back, as `umars` is not a symbol
+    let err = Coins::from_human_str(&s, &metadata()).unwrap_err();
+    assert_eq!(
+        err,
+        CoinsError::UnknownSymbol {
+            symbol: "umars".to_string(),
+        }
+    );
+
+    let coins = Coins::from_str("1uatom,2uosmo").unwrap();
+    assert_eq!(coins.to_human_string(&metadata()).unwrap(), "0.000001 ATOM, 0.000002 OSMO");
+}
+
+#[test]
+fn round_tripping_human_strings() {
+    for s in ["12500000uatom,3000001uosmo,7ibc/1234ABCD", "1uatom", ""] {
+        let coins = Coins::from_str(s).unwrap();
+        let human = coins.to_human_string(&metadata()).unwrap();
+        assert_eq!(Coins::from_human_str(&human, &metadata()).unwrap(), coins, "{}", human);
+    }
+
+    // 38 decimals is the most a 128-bit amount can have
+    let mut metadata = metadata();
+    metadata[0].decimals = 38;
+    let coins = Coins::from_str(&format!("{}uatom", u128::MAX)).unwrap();
+    let human = coins.to_human_string(&metadata).unwrap();
+    assert_eq!(human, "3.40282366920938463463374607431768211455 ATOM");
+    assert_eq!(Coins::from_human_str(&human, &metadata).unwrap(), coins);
+
+    // any more are refused rather than padded to, however large
+    for decimals in [39, u32::MAX] {
+        metadata[0].decimals = decimals;
+        let err = coins.to_human_string(&metadata).unwrap_err();
+        assert_eq!(
+            err,
+            CoinsError::ExponentOverflow {
+                exp: decimals,
+            }
+        );
+        let err = Coins::from_human_str("1 ATOM", &metadata).unwrap_err();
+        assert_eq!(
+            err,
+            CoinsError::ExponentOverflow {
+                exp: decimals,
+            }
+        );
+    }
+}
+
+#[test]
+fn parsing_display_strings() {
+    let mut exponents = PerDenom::new();
+    exponents.insert("uatom", 6).unwrap();
+    exponents.insert("uosmo", 6).unwrap();
+
+    let coins =
+        Coins::from_display_str("1.5uatom, 0.000001uosmo,7ibc/1234ABCD", &exponents).unwrap();
+    assert_eq!(coins, Coins::from_str("1500000uatom,1uosmo,7ibc/1234ABCD").unwrap());
+
+    let err = Coins::from_display_str("0.0000001uatom", &exponents).unwrap_err();
+    assert!(err.to_string().contains("more than 6 decimal places"));
+
+    // denoms without an exponent only take integer amounts
+    assert!(Coins::from_display_str("1.5ibc/1234ABCD", &exponents).is_err());
+
+    assert!(Coins::from_display_str("uatom", &exponents).is_err());
+    assert!(Coins::from_display_str("1.5", &exponents).is_err());
+    assert!(Coins::from_display_str("1..5uatom", &exponents).is_err());
+    assert!(Coins::from_display_str("1uatom,2uatom", &exponents).is_err());
+
+    // separators between digits, on either side of the decimal point
+    let coins = Coins::from_display_str("1_000.000_5uatom,1_000ibc/1234ABCD", &exponents).unwrap();
+    assert_eq!(coins, Coins::from_str("1000000500uatom,1000ibc/1234ABCD").unwrap());
+    for s in ["_1uatom", "1_uatom", "1__0uatom", "1_.5uatom", "1._5uatom"] {
+        assert!(Coins::from_display_str(s, &exponents).is_err(), "{}", s);
+    }
+
+    // denoms are validated the same way as by `from_str`, so whitespace doesn't end up in them
+    let err = Coins::from_display_str("15 uatom", &exponents).unwrap_err();
+    assert!(matches!(err, CoinsError::InvalidDenom { .. }), "{:?}", err);
+    let err = Coins::from_display_str("15u$d", &exponents).unwrap_err();
+    assert!(matches!(err, CoinsError::InvalidDenom { .. }), "{:?}", err);
+}
@@ -0,0 +1,151 @@
+use std::str::FromStr;
+
+use cosmwasm_std::{coin, Uint128};
+use cw_coins::Coins;
+
+#[test]
+fn iterating_by_reference() {
+    let coins = Coins::from_str("12uosmo,34uatom").unwrap();
+
+    let mut entries = vec![];
+    for (denom, amount) in &coins {
+        entries.push((denom, *amount));
+    }
+    assert_eq!(entries, vec![("uatom", Uint128::new(34)), ("uosmo", Uint128::new(12))]);
+}
+
+#[test]
+fn iterating_coins_lazily() {
+    let coins = Coins::from_str("12uosmo,34uatom").unwrap();
+
+    let mut iter = coins.iter();
+    assert_eq!(iter.next(), Some(coin(34, "uatom")));
+    assert_eq!(iter.next(), Some(coin(12, "uosmo")));
+    assert_eq!(iter.next(), None);
+
+    assert_eq!(coins.iter().collect::<Vec<_>>(), coins.to_vec());
+}
+
+#[test]
+fn collecting() {
+    let funds = vec![coin(12, "uosmo"), coin(0, "umars"), coin(34, "uatom"), coin(1, "uosmo")];
+
+    let coins = funds.clone().into_iter().collect::<Coins>();
+    assert_eq!(coins.to_string(), "34uatom,13uosmo");
+
+    let err = Coins::try_from_iter(funds).unwrap_err();
+    assert!(err.to_string().contains("duplicate denom: uosmo"));
+
+    let coins = Coins::try_from_iter(vec![coin(12, "uosmo"), coin(34, "uatom")]).unwrap();
+    assert_eq!(coins.to_string(), "34uatom,12uosmo");
+}
+
+#[test]
+fn collecting_pairs() {
+    let pairs =
+        vec![("uosmo".to_string(), Uint128::new(12)), ("uatom".to_string(), Uint128::new(34))];
+    let coins = pairs.into_iter().collect::<Coins>();
+    assert_eq!(coins.to_string(), "34uatom,12uosmo");
+}
+
+#[test]
+fn collecting_pairs_like_coins() {
+    let funds = vec![coin(12, "uosmo"), coin(0, "umars"), coin(34, "uatom"), coin(1, "uosmo")];
+    let pairs = funds
+        .iter()
+        .map(|coin| (coin.denom.clone(), coin.amount))
+        .collect::<Vec<_>>();
+
+    // both sum the amounts of duplicate denoms
+    let coins = pairs.clone().into_iter().collect::<Coins>();
+    assert_eq!(coins, funds.clone().into_iter().collect::<Coins>());
+    assert_eq!(coins.to_string(), "34uatom,13uosmo");
+
+    // and zero amounts are left out whatever their denom
+    let blank = vec![(String::new(), Uint128::zero())];
+    assert!(blank.into_iter().collect::<Coins>().is_empty());
+
+    // rejecting duplicates instead takes `from_pairs` or `try_from_iter`
+    let err = Coins::from_pairs(pairs).unwrap_err();
+    assert!(err.to_string().contains("duplicate denom: uosmo"));
+    assert_eq!(Coins::try_from_iter(funds).unwrap_err(), err);
+}
+
+#[test]
+fn extending() {
+    let mut coins = Coins::from_str("12uosmo").unwrap();
+    coins.extend(vec![coin(34, "uatom"), coin(1, "uosmo"), coin(0, "umars")]);
+    assert_eq!(coins.to_string(), "34uatom,13uosmo");
+}
+
+#[test]
+#[should_panic(expected = "overflow")]
+fn extending_overflow_panics() {
+    let mut coins = Coins::from_str("1uatom").unwrap();
+    coins.extend(vec![coin(u128::MAX, "uatom")]);
+}
+
+#[test]
+fn iterating_backwards() {
+    let coins = Coins::from_str("12uosmo,34uatom,56umars").unwrap();
+
+    let denoms = coins
+        .iter()
+        .rev()
+        .map(|coin| coin.denom)
+        .collect::<Vec<_>>();
+    assert_eq!(denoms, ["uosmo", "umars", "uatom"]);
+
+    let denoms = (&coins)
+        .into_iter()
+        .rev()
+        .map(|(denom, _)| denom)
+        .collect::<Vec<_>>();
+    assert_eq!(denoms, ["uosmo", "umars", "uatom"]);
+
+    let mut iter = coins.clone().into_iter();
+    assert_eq!(iter.len(), 3);
+    assert_eq!(iter.next_back(), Some(coin(12, "uosmo")));
+    assert_eq!(iter.next(), Some(coin(34, "uatom")));
+    assert_eq!(iter.len(), 1);
+    assert_eq!(iter.next_back(), Some(coin(56, "umars")));
+    assert_eq!(iter.next(), None);
+
+    assert_eq!(coins.iter().len(), 3);
+    assert_eq!((&coins).into_iter().len(), 3);
+}
+
+#[test]
+fn consuming_denoms_and_amounts() {
+    let coins = Coins::from_str("12uosmo,34uatom").unwrap();
+
+    let denoms = coins.clone().into_denoms().collect::<Vec<_>>();
+    assert_eq!(denoms, ["uatom", "uosmo"]);
+
+    let amounts = coins.into_amounts().collect::<Vec<_>>();
+    assert_eq!(amounts, [Uint128::new(34), Uint128::new(12)]);
+}
+
+#[test]
+fn iterating_over_a_range() {
+    let coins = Coins::from_str(
+        "1factory/osmo1a/x,2factory/osmo1a/y,3factory/osmo1ab/z,4factory/osmo1b/x,5uatom,6uosmo",
+    )
+    .unwrap();
+
+    let denoms = |range: cw_coins::iter::Range| range.map(|coin| coin.denom).collect::<Vec<_>>();
+
+    // a prefix scan
+    assert_eq!(
+        denoms(coins.range("factory/osmo1a/".."factory/osmo1a0")),
+        ["factory/osmo1a/x", "factory/osmo1a/y"]
+    );
+
+    assert_eq!(denoms(coins.range("uatom"..)), ["uatom", "uosmo"]);
+    assert_eq!(denoms(coins.range(.."factory/osmo1a/y")), ["factory/osmo1a/x"]);
+    assert_eq!(coins.range("uatom"..).next_back(), Some(coin(6, "uosmo")));
+    assert_eq!(denoms(coins.range("uatom"..="uatom")), ["uatom"]);
+    assert_eq!(coins.range(..).count(), 6);
+    assert_eq!(coins.range("v"..).next(), None);
+    assert_eq!(coins.range("uosmo"..).next(), Some(coin(6, "uosmo")));
+}
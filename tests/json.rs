@@ -0,0 +1,57 @@
+#![cfg(feature = "json")]
+
+use cw_coins::json::{canonicalize, to_canonical_string};
+use serde_json::json;
+
+#[test]
+fn canonicalizing() {
+    let mut value = json!({
+        "deposit": {
+            "funds": {
+                "uosmo": "0042",
+                "uatom": "12345",
+                "umars": "0"
+            },
+            "recipient": "osmo1234abcd",
+            "limits": [{ "uatom": "000" }, { "amount": "not a number" }]
+        }
+    });
+
+    canonicalize(&mut value, |denom| denom.starts_with('u'));
+
+    assert_eq!(
+        value,
+        json!({
+            "deposit": {
+                "funds": {
+                    "uatom": "12345",
+                    "uosmo": "42"
+                },
+                "recipient": "osmo1234abcd",
+                "limits": [{}, { "amount": "not a number" }]
+            }
+        })
+    );
+
+    assert_eq!(
+        to_canonical_string(&json!({ "funds": { "uosmo": "07", "uatom": "1" } }), |_| true),
+        r#"{"funds":{"uatom":"1","uosmo":"7"}}"#
+    );
+}
+
+#[test]
+fn canonicalizing_only_denom_keys() {
+    let is_denom = |denom: &str| denom != "memo";
+
+    // fields that merely look like amounts are left alone
+    let value = json!({ "memo": "007" });
+    assert_eq!(to_canonical_string(&value, is_denom), r#"{"memo":"007"}"#);
+
+    // as are maps with keys that aren't valid denoms, even if the caller accepts them
+    let value = json!({ "1st": "01", "u atom": "02" });
+    assert_eq!(to_canonical_string(&value, |_| true), r#"{"1st":"01","u atom":"02"}"#);
+
+    // nor are amounts with a sign or separators rewritten
+    let value = json!({ "uatom": "+1" });
+    assert_eq!(to_canonical_string(&value, is_denom), r#"{"uatom":"+1"}"#);
+}
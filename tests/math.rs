@@ -0,0 +1,444 @@
+use std::collections::BTreeMap;
+use std::str::FromStr;
+
+use cosmwasm_std::{coin, Coin, Decimal, Uint128, Uint256};
+use cw_coins::{Coins, CoinsError};
+
+fn coins(s: &str) -> Coins {
+    Coins::from_str(s).unwrap()
+}
+
+fn max(denom: &str) -> Coins {
+    coins(&format!("{}{}", u128::MAX, denom))
+}
+
+#[test]
+fn adding() {
+    let a = coins("100uatom,50uosmo");
+    let b = coins("1uatom,25umars");
+
+    let expected = coins("101uatom,25umars,50uosmo");
+    assert_eq!(a.clone() + b.clone(), expected);
+    assert_eq!(a.clone() + &b, expected);
+    assert_eq!(a.checked_add(&b).unwrap(), expected);
+
+    let mut c = a.clone();
+    c += b;
+    c += coin(5, "ujuno");
+    // adding zero doesn't create a zero-amount entry
+    c += coin(0, "uusdc");
+    assert_eq!(c, coins("101uatom,5ujuno,25umars,50uosmo"));
+
+    assert_eq!(a.clone() + coin(1, "uosmo"), coins("100uatom,51uosmo"));
+    assert_eq!(a.checked_add_coin(&coin(1, "uosmo")).unwrap(), coins("100uatom,51uosmo"));
+
+    // overflows
+    let err = max("uatom").checked_add(&a).unwrap_err();
+    assert_eq!(err.to_string(), format!("overflow: cannot add 100uatom to {}uatom", u128::MAX));
+    max("uatom")
+        .checked_add_coin(&coin(1, "uatom"))
+        .unwrap_err();
+}
+
+#[test]
+#[should_panic(expected = "overflow: cannot add 1uatom")]
+fn adding_overflow_panics() {
+    let _ = max("uatom") + coins("1uatom");
+}
+
+#[test]
+fn subtracting() {
+    let a = coins("100uatom,50uosmo");
+    let b = coins("1uatom,50uosmo");
+
+    // denoms reaching zero are removed
+    let expected = coins("99uatom");
+    assert_eq!(a.clone() - b.clone(), expected);
+    assert_eq!(a.clone() - &b, expected);
+    assert_eq!(a.checked_sub(&b).unwrap(), expected);
+
+    let mut c = a.clone();
+    c -= coin(100, "uatom");
+    c -= coin(0, "umars");
+    assert_eq!(c, coins("50uosmo"));
+    c -= coins("50uosmo");
+    assert!(c.is_empty());
+
+    assert_eq!(a.clone() - coin(1, "uosmo"), coins("100uatom,49uosmo"));
+    assert_eq!(a.checked_sub_coin(&coin(1, "uosmo")).unwrap(), coins("100uatom,49uosmo"));
+
+    // underflows, including of denoms not in the collection
+    let err = a.checked_sub(&coins("101uatom")).unwrap_err();
+    assert_eq!(err.to_string(), "insufficient uatom: available 100, needed 101");
+    let err = a.checked_sub_coin(&coin(1, "umars")).unwrap_err();
+    assert_eq!(
+        err,
+        CoinsError::Underflow {
+            denom: "umars".to_string(),
+            needed: Uint128::new(1),
+            available: Uint128::zero(),
+        }
+    );
+}
+
+#[test]
+#[should_panic(expected = "insufficient uatom: available 1, needed 2")]
+fn subtracting_underflow_panics() {
+    let _ = coins("1uatom") - coins("2uatom");
+}
+
+#[test]
+fn saturating_subtracting() {
+    let allowance = coins("100uatom,50uosmo");
+    let spent = coins("30uatom,80uosmo,10umars");
+
+    assert_eq!(allowance.saturating_sub(&spent), coins("70uatom"));
+    assert_eq!(spent.saturating_sub(&allowance), coins("30uosmo,10umars"));
+    assert_eq!(allowance.saturating_sub(&Coins::default()), allowance);
+    assert!(allowance.saturating_sub(&allowance).is_empty());
+}
+
+#[test]
+fn subtracting_with_remainder() {
+    let balance = coins("100uatom,50uosmo,20umars");
+    let order = coins("30uatom,80uosmo,20umars,10ujuno");
+
+    let (left, unmet) = balance.sub_with_remainder(&order);
+    assert_eq!(left, coins("70uatom"));
+    assert_eq!(unmet, coins("30uosmo,10ujuno"));
+    // the deducted amounts plus what is left add back up to the balance
+    assert_eq!(order.checked_sub(&unmet).unwrap() + left.clone(), balance);
+    assert_eq!(left, balance.saturating_sub(&order));
+
+    let (left, unmet) = balance.sub_with_remainder(&Coins::default());
+    assert_eq!(left, balance);
+    assert!(unmet.is_empty());
+    let (left, unmet) = Coins::default().sub_with_remainder(&order);
+    assert!(left.is_empty());
+    assert_eq!(unmet, order);
+}
+
+#[test]
+fn multiplying() {
+    let a = coins("100uatom,50uosmo");
+
+    assert_eq!(a.clone() * Uint128::new(3), coins("300uatom,150uosmo"));
+    assert_eq!(a.checked_mul(Uint128::new(3)).unwrap(), coins("300uatom,150uosmo"));
+    assert!(a.checked_mul(Uint128::zero()).unwrap().is_empty());
+    // any unsigned integer converts into the factor
+    assert_eq!(a.checked_mul(3u64).unwrap(), coins("300uatom,150uosmo"));
+
+    let mut b = a.clone();
+    b *= Uint128::new(2);
+    assert_eq!(b, coins("200uatom,100uosmo"));
+
+    let err = (max("uosmo") + coin(1, "uatom"))
+        .checked_mul(Uint128::new(2))
+        .unwrap_err();
+    assert!(err
+        .to_string()
+        .contains(&format!("cannot multiply {}uosmo by 2", u128::MAX)));
+}
+
+#[test]
+#[should_panic(expected = "cannot multiply")]
+fn multiplying_overflow_panics() {
+    let _ = max("uatom") * Uint128::new(2);
+}
+
+#[test]
+fn multiplying_by_decimals() {
+    let a = coins("100uatom,3uosmo,1umars");
+    let ratio = Decimal::percent(50);
+
+    assert_eq!(a.mul_floor(ratio).unwrap(), coins("50uatom,1uosmo"));
+    assert_eq!(a.mul_ceil(ratio).unwrap(), coins("50uatom,2uosmo,1umars"));
+
+    // exact results are not rounded either way
+    let ratio = Decimal::percent(250);
+    assert_eq!(a.mul_floor(ratio).unwrap(), coins("250uatom,7uosmo,2umars"));
+    assert_eq!(a.mul_ceil(ratio).unwrap(), coins("250uatom,8uosmo,3umars"));
+
+    assert!(a.mul_ceil(Decimal::zero()).unwrap().is_empty());
+    assert_eq!(max("uatom").mul_floor(Decimal::one()).unwrap(), max("uatom"));
+
+    let err = max("uatom").mul_floor(Decimal::percent(101)).unwrap_err();
+    assert_eq!(
+        err,
+        CoinsError::MulOverflow {
+            denom: "uatom".to_string(),
+            amount: Uint128::MAX,
+            factor: "1.01".to_string(),
+        }
+    );
+}
+
+#[test]
+fn dividing_with_remainder() {
+    let a = coins("100uatom,3uosmo,7umars");
+
+    let (quotient, remainder) = a.checked_div(3).unwrap();
+    assert_eq!(quotient, coins("33uatom,1uosmo,2umars"));
+    assert_eq!(remainder, coins("1uatom,1umars"));
+    assert_eq!(quotient * Uint128::new(3) + remainder, a);
+
+    // dividing by more than every amount leaves everything in the remainder
+    let (quotient, remainder) = a.checked_div(1000).unwrap();
+    assert!(quotient.is_empty());
+    assert_eq!(remainder, a);
+
+    assert_eq!(a.checked_div(1).unwrap(), (a.clone(), Coins::default()));
+    assert!(a.checked_div(0).is_err());
+}
+
+#[test]
+fn summing() {
+    let funds = [coin(100, "uatom"), coin(0, "uosmo"), coin(23, "uatom"), coin(5, "umars")];
+    assert_eq!(funds.iter().cloned().sum::<Coins>(), coins("123uatom,5umars"));
+    assert_eq!(funds.iter().sum::<Coins>(), coins("123uatom,5umars"));
+
+    let balances = [coins("100uatom"), coins("23uatom,5umars"), Coins::default()];
+    assert_eq!(balances.iter().sum::<Coins>(), coins("123uatom,5umars"));
+    assert_eq!(balances.into_iter().sum::<Coins>(), coins("123uatom,5umars"));
+
+    assert!(Vec::<Coin>::new().into_iter().sum::<Coins>().is_empty());
+}
+
+#[test]
+#[should_panic(expected = "overflow")]
+fn summing_overflow_panics() {
+    let _ = [max("uatom"), coins("1uatom")].into_iter().sum::<Coins>();
+}
+
+#[test]
+fn try_summing() {
+    let positions = vec![coins("100uatom"), coins("23uatom,5umars"), Coins::default()];
+    assert_eq!(Coins::try_sum(positions).unwrap(), coins("123uatom,5umars"));
+    assert!(Coins::try_sum(vec![]).unwrap().is_empty());
+
+    let err = Coins::try_sum(vec![coins("5umars"), max("uatom"), coins("1uatom")]).unwrap_err();
+    assert!(err.to_string().contains("cannot add 1uatom"));
+}
+
+#[test]
+fn splitting_by_weight() {
+    let a = coins("100uatom,10uosmo,1umars");
+
+    let parts = a
+        .split_weighted(&[Decimal::percent(50), Decimal::percent(50)])
+        .unwrap();
+    assert_eq!(parts, vec![coins("50uatom,5uosmo,1umars"), coins("50uatom,5uosmo")]);
+
+    // weights are relative to their sum, and dust goes to the largest weights first
+    let weights = [Decimal::one(), Decimal::percent(300), Decimal::zero(), Decimal::percent(300)];
+    let parts = a.split_weighted(&weights).unwrap();
+    assert_eq!(
+        parts,
+        vec![
+            coins("14uatom,1uosmo"),
+            coins("43uatom,5uosmo,1umars"),
+            Coins::default(),
+            coins("43uatom,4uosmo"),
+        ]
+    );
+    assert_eq!(parts.into_iter().sum::<Coins>(), a);
+
+    assert!(a.split_weighted(&[]).is_err());
+    assert_eq!(a.split_weighted(&[Decimal::zero()]), Err(CoinsError::ZeroWeight));
+}
+
+#[test]
+fn splitting_evenly() {
+    let a = coins("100uatom,3uosmo,7umars");
+
+    let (parts, remainder) = a.split_evenly(3).unwrap();
+    assert_eq!(parts, vec![coins("33uatom,1uosmo,2umars"); 3]);
+    assert_eq!(remainder, coins("1uatom,1umars"));
+    assert_eq!(parts.into_iter().sum::<Coins>() + remainder, a);
+
+    assert!(matches!(a.split_evenly(0), Err(CoinsError::DivideByZero { .. })));
+
+    // the number of parts is bounded, as each of them is allocated
+    let (parts, _) = a.split_evenly(Coins::MAX_SPLIT_PARTS).unwrap();
+    assert_eq!(parts.len(), Coins::MAX_SPLIT_PARTS);
+    assert_eq!(
+        a.split_evenly(usize::MAX),
+        Err(CoinsError::TooManyParts {
+            parts: usize::MAX,
+            max: Coins::MAX_SPLIT_PARTS,
+        })
+    );
+}
+
+#[test]
+fn applying_fees() {
+    let a = coins("1000uatom,99uosmo,1umars");
+
+    let (fee, net) = a.apply_fee(Decimal::permille(15)).unwrap();
+    assert_eq!(fee, coins("15uatom,1uosmo"));
+    assert_eq!(net, coins("985uatom,98uosmo,1umars"));
+
+    assert_eq!(a.apply_fee(Decimal::zero()).unwrap(), (Coins::default(), a.clone()));
+    assert_eq!(a.apply_fee(Decimal::one()).unwrap(), (a.clone(), Coins::default()));
+    assert_eq!(
+        a.apply_fee(Decimal::percent(101)),
+        Err(CoinsError::FeeRateTooHigh {
+            rate: Decimal::percent(101),
+        })
+    );
+}
+
+#[test]
+fn interpolating() {
+    let start = coins("100uatom,10uosmo");
+    let end = coins("200uatom,5umars");
+
+    assert_eq!(Coins::lerp(&start, &end, Decimal::zero()).unwrap(), start);
+    assert_eq!(Coins::lerp(&start, &end, Decimal::one()).unwrap(), end);
+    assert_eq!(
+        Coins::lerp(&start, &end, Decimal::percent(25)).unwrap(),
+        coins("125uatom,8uosmo,1umars")
+    );
+
+    // linear vesting from nothing
+    let total = coins("1000uatom");
+    let vested = Coins::lerp(&Coins::default(), &total, Decimal::from_ratio(1u128, 3u128)).unwrap();
+    assert_eq!(vested, coins("333uatom"));
+
+    assert_eq!(
+        Coins::lerp(&start, &end, Decimal::percent(101)),
+        Err(CoinsError::InterpolationOutOfRange {
+            t: Decimal::percent(101),
+        })
+    );
+}
+
+#[test]
+fn multiplying_by_ratios() {
+    let a = coins("100uatom,3uosmo,1umars");
+
+    // e.g. the coins redeemed for 1 out of 3 shares
+    let ratio = a
+        .checked_mul_ratio(Uint128::new(1), Uint128::new(3))
+        .unwrap();
+    assert_eq!(ratio, coins("33uatom,1uosmo"));
+    assert_eq!(a.checked_mul_ratio(1u128, 3u64).unwrap(), ratio);
+
+    // the intermediate product may exceed 128 bits
+    let ratio = max("uatom")
+        .checked_mul_ratio(Uint128::MAX, Uint128::MAX)
+        .unwrap();
+    assert_eq!(ratio, max("uatom"));
+
+    let err = max("uatom")
+        .checked_mul_ratio(Uint128::new(3), Uint128::new(2))
+        .unwrap_err();
+    assert!(err.to_string().contains("by 3/2"));
+    assert!(a
+        .checked_mul_ratio(Uint128::new(1), Uint128::zero())
+        .is_err());
+}
+
+#[test]
+fn scaling_by_powers_of_ten() {
+    let a = coins("12uatom,3uosmo");
+
+    let scaled = a.scale_up(6).unwrap();
+    assert_eq!(scaled, coins("12000000uatom,3000000uosmo"));
+    assert_eq!(scaled.scale_down(6).unwrap(), (a.clone(), Coins::default()));
+
+    let (scaled, remainder) = coins("1500000uatom,999uosmo").scale_down(6).unwrap();
+    assert_eq!(scaled, coins("1uatom"));
+    assert_eq!(remainder, coins("500000uatom,999uosmo"));
+
+    assert_eq!(a.scale_up(0).unwrap(), a);
+    assert!(max("uatom").scale_up(1).is_err());
+    assert_eq!(
+        a.scale_up(39),
+        Err(CoinsError::ExponentOverflow {
+            exp: 39,
+        })
+    );
+    assert!(a.scale_down(39).is_err());
+}
+
+#[test]
+fn multiplying_wide() {
+    let a = coins("100uatom,3uosmo");
+
+    // a price of 1.5 with 36 decimal places, which doesn't fit in 128 bits
+    let unit = Uint256::from(10u128.pow(18)) * Uint256::from(10u128.pow(18));
+    let price = unit * Uint256::from(3u8) / Uint256::from(2u8);
+    assert_eq!(a.checked_mul_wide(price, unit).unwrap(), coins("150uatom,4uosmo"));
+
+    assert!(max("uatom").checked_mul_wide(price, unit).is_err());
+    assert!(max("uatom")
+        .checked_mul_wide(Uint256::MAX, Uint256::MAX)
+        .is_err());
+    assert!(a.checked_mul_wide(price, Uint256::zero()).is_err());
+
+    let products = max("uatom").full_mul(Uint128::MAX);
+    assert_eq!(products.get("uatom"), Some(&Uint128::MAX.full_mul(Uint128::MAX)));
+    assert!(a.full_mul(Uint128::zero()).is_empty());
+}
+
+#[test]
+fn absolute_differences() {
+    let expected = coins("100uatom,50uosmo,7umars");
+    let actual = coins("98uatom,50uosmo,3uusdc");
+
+    let diff = coins("2uatom,7umars,3uusdc");
+    assert_eq!(expected.abs_diff(&actual), diff);
+    assert_eq!(actual.abs_diff(&expected), diff);
+
+    assert!(expected.abs_diff(&expected).is_empty());
+    assert_eq!(max("uatom").abs_diff(&Coins::default()), max("uatom"));
+}
+
+#[test]
+fn applying_ratios() {
+    let collateral = coins("1000uatom,10uosmo");
+
+    let mut factors = BTreeMap::new();
+    factors.insert("uatom".to_string(), (Uint128::new(4), Uint128::new(5)));
+    factors.insert("uosmo".to_string(), (Uint128::new(1), Uint128::new(3)));
+    factors.insert("umars".to_string(), (Uint128::new(1), Uint128::new(2)));
+    assert_eq!(collateral.apply_ratios(&factors).unwrap(), coins("800uatom,3uosmo"));
+
+    let err = coins("1uusdc").apply_ratios(&factors).unwrap_err();
+    assert!(err.to_string().contains("no ratio given for denom uusdc"));
+
+    factors.insert("uosmo".to_string(), (Uint128::new(1), Uint128::zero()));
+    assert!(collateral.apply_ratios(&factors).is_err());
+
+    factors.insert("uatom".to_string(), (Uint128::new(2), Uint128::new(1)));
+    assert!(max("uatom").apply_ratios(&factors).is_err());
+}
+
+#[test]
+fn bulk_operations() {
+    let mut balance = max("uosmo") + coins("100uatom,20umars");
+
+    let report = balance.add_many(&coins("1uosmo,50uatom,5ujuno"));
+    assert_eq!(report.len(), 3);
+    assert_eq!(report.get("uatom"), Some(&Ok(Uint128::new(150))));
+    assert_eq!(report.get("ujuno"), Some(&Ok(Uint128::new(5))));
+    assert!(matches!(report.get("uosmo"), Some(Err(CoinsError::Overflow { .. }))));
+    // the overflowing denom is left unchanged, the others are added
+    assert_eq!(balance, max("uosmo") + coins("150uatom,20umars,5ujuno"));
+
+    let report = balance.deduct_many(&coins("150uatom,30umars,1ujuno"));
+    assert_eq!(report.get("uatom"), Some(&Ok(Uint128::zero())));
+    assert_eq!(report.get("ujuno"), Some(&Ok(Uint128::new(4))));
+    assert_eq!(
+        report.get("umars"),
+        Some(&Err(CoinsError::Underflow {
+            denom: "umars".to_string(),
+            needed: Uint128::new(30),
+            available: Uint128::new(20),
+        }))
+    );
+    assert_eq!(balance, max("uosmo") + coins("20umars,4ujuno"));
+
+    assert!(balance.add_many(&Coins::default()).is_empty());
+}
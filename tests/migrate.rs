@@ -0,0 +1,112 @@
+#![cfg(feature = "unstable")]
+
+use std::str::FromStr;
+
+use cosmwasm_std::testing::MockStorage;
+use cosmwasm_std::{from_json, to_json_vec, Coin, Storage};
+use cw_coins::{Coins, LedgerMigration, MigrationStep};
+
+const MIGRATION: LedgerMigration = LedgerMigration::new("balances", "migration");
+
+/// The key cw-storage-plus would use for `Map::new("balances").key(owner)`
+fn key(owner: &str) -> Vec<u8> {
+    let mut key = vec![0, 8];
+    key.extend_from_slice(b"balances");
+    key.extend_from_slice(owner.as_bytes());
+    key
+}
+
+#[test]
+fn migrating_in_batches() {
+    let mut storage = MockStorage::new();
+    for (owner, coins) in [("alice", "100uluna"), ("bob", "1uluna"), ("charlie", "2uluna")] {
+        let coins = Coins::from_str(coins).unwrap();
+        storage.set(&key(owner), serde_json::to_string(&coins).unwrap().as_bytes());
+    }
+    // an entry belonging to another namespace, which must not be touched
+    storage.set(b"\x00\x08balancer", b"whatever");
+
+    // drop balances consisting of nothing but dust
+    let mut seen = vec![];
+    let mut transform = |owner: &_, coins: Coins| {
+        seen.push((owner as &cosmwasm_std::Addr).to_string());
+        Ok(coins
+            .into_iter()
+            .filter(|coin| coin.amount.u128() > 1)
+            .collect())
+    };
+
+    let step = MIGRATION.step(&mut storage, 2, &mut transform).unwrap();
+    assert_eq!(
+        step,
+        MigrationStep {
+            processed: 2,
+            done: false,
+        }
+    );
+    assert!(!MIGRATION.is_done(&storage).unwrap());
+
+    let step = MIGRATION.step(&mut storage, 2, &mut transform).unwrap();
+    assert_eq!(
+        step,
+        MigrationStep {
+            processed: 1,
+            done: true,
+        }
+    );
+    assert!(MIGRATION.is_done(&storage).unwrap());
+    assert_eq!(seen, vec!["alice", "bob", "charlie"]);
+
+    // further steps are no-ops
+    let step = MIGRATION
+        .step(&mut storage, 2, |_, _| panic!("should not be called"))
+        .unwrap();
+    assert_eq!(step.processed, 0);
+
+    let load = |owner| {
+        storage
+            .get(&key(owner))
+            .map(|v| from_json::<Coins>(&v).unwrap())
+    };
+    assert_eq!(load("alice"), Some(Coins::from_str("100uluna").unwrap()));
+    assert_eq!(load("bob"), None);
+    assert_eq!(load("charlie"), Some(Coins::from_str("2uluna").unwrap()));
+    assert_eq!(storage.get(b"\x00\x08balancer"), Some(b"whatever".to_vec()));
+}
+
+#[test]
+fn migrating_modified_entries() {
+    let mut storage = MockStorage::new();
+    for (owner, coins) in [("alice", "100uluna,5uusd"), ("bob", "7uusd")] {
+        let coins = Coins::from_str(coins).unwrap();
+        storage.set(&key(owner), &to_json_vec(&coins).unwrap());
+    }
+
+    // rename `uluna` to `ulunc`, and halve everything else
+    let step = MIGRATION
+        .step(&mut storage, 10, |_, coins| {
+            Ok(coins
+                .into_iter()
+                .map(|coin| match coin.denom.as_str() {
+                    "uluna" => Coin::new(coin.amount.u128(), "ulunc"),
+                    _ => Coin::new(coin.amount.u128() / 2, coin.denom),
+                })
+                .collect())
+        })
+        .unwrap();
+    assert_eq!(
+        step,
+        MigrationStep {
+            processed: 2,
+            done: true,
+        }
+    );
+
+    let load = |owner| {
+        storage
+            .get(&key(owner))
+            .map(|v| from_json::<Coins>(&v).unwrap())
+    };
+    assert_eq!(load("alice"), Some(Coins::from_str("100ulunc,2uusd").unwrap()));
+    assert_eq!(load("bob"), Some(Coins::from_str("3uusd").unwrap()));
+}
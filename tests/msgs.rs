@@ -0,0 +1,147 @@
+#![cfg(feature = "unstable")]
+
+use std::str::FromStr;
+
+use cosmwasm_std::{coin, to_json_binary, BankMsg, CosmosMsg, Empty, WasmMsg};
+use cw_coins::{Coins, SpendBudget};
+
+#[test]
+fn collecting_from_msgs() {
+    let msgs: Vec<CosmosMsg<Empty>> = vec![
+        CosmosMsg::Bank(BankMsg::Send {
+            to_address: "alice".to_string(),
+            amount: vec![coin(100, "uatom"), coin(50, "uosmo")],
+        }),
+        CosmosMsg::Bank(BankMsg::Burn {
+            amount: vec![coin(25, "uosmo")],
+        }),
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "contract".to_string(),
+            msg: to_json_binary(&Empty {}).unwrap(),
+            funds: vec![coin(10, "uatom"), coin(1, "umars")],
+        }),
+        CosmosMsg::Wasm(WasmMsg::ClearAdmin {
+            contract_addr: "contract".to_string(),
+        }),
+    ];
+
+    let coins = Coins::collect_from_msgs(&msgs).unwrap();
+    assert_eq!(coins, Coins::from_str("110uatom,1umars,75uosmo").unwrap());
+
+    // no messages, no funds
+    assert!(Coins::collect_from_msgs::<Empty>(&[]).unwrap().is_empty());
+
+    // overflowing the total is an error
+    let msgs: Vec<CosmosMsg<Empty>> = vec![
+        CosmosMsg::Bank(BankMsg::Burn {
+            amount: vec![coin(u128::MAX, "uatom")],
+        }),
+        CosmosMsg::Bank(BankMsg::Burn {
+            amount: vec![coin(1, "uatom")],
+        }),
+    ];
+    Coins::collect_from_msgs(&msgs).unwrap_err();
+}
+
+#[test]
+fn collecting_from_uninspectable_msgs() {
+    let msgs: Vec<CosmosMsg<Empty>> = vec![
+        CosmosMsg::Bank(BankMsg::Burn {
+            amount: vec![coin(1, "uatom")],
+        }),
+        CosmosMsg::Custom(Empty {}),
+    ];
+    let err = Coins::collect_from_msgs(&msgs).unwrap_err();
+    assert!(err
+        .to_string()
+        .contains("cannot inspect the funds sent by a custom message"));
+}
+
+#[cfg(feature = "stargate")]
+#[test]
+fn collecting_from_stargate_msgs() {
+    let msgs: Vec<CosmosMsg<Empty>> = vec![
+        CosmosMsg::Ibc(cosmwasm_std::IbcMsg::Transfer {
+            channel_id: "channel-0".to_string(),
+            to_address: "cosmos1abcd".to_string(),
+            amount: coin(5, "uatom"),
+            timeout: cosmwasm_std::Timestamp::from_seconds(0).into(),
+        }),
+        CosmosMsg::Gov(cosmwasm_std::GovMsg::Vote {
+            proposal_id: 1,
+            vote: cosmwasm_std::VoteOption::Yes,
+        }),
+    ];
+    let coins = Coins::collect_from_msgs(&msgs).unwrap();
+    assert_eq!(coins, Coins::from_str("5uatom").unwrap());
+
+    let msgs: Vec<CosmosMsg<Empty>> = vec![CosmosMsg::Stargate {
+        type_url: "/cosmos.bank.v1beta1.MsgSend".to_string(),
+        value: Default::default(),
+    }];
+    let err = Coins::collect_from_msgs(&msgs).unwrap_err();
+    assert!(err
+        .to_string()
+        .contains("cannot inspect the funds sent by a stargate message"));
+}
+
+#[test]
+fn charging_spend_budget() {
+    let mut budget = SpendBudget(Coins::from_str("100uatom,50uosmo").unwrap());
+
+    let send = |amount| -> Vec<CosmosMsg<Empty>> {
+        vec![CosmosMsg::Bank(BankMsg::Send {
+            to_address: "alice".to_string(),
+            amount,
+        })]
+    };
+
+    budget
+        .charge(&send(vec![coin(60, "uatom"), coin(50, "uosmo")]))
+        .unwrap();
+    assert_eq!(budget.0, Coins::from_str("40uatom").unwrap());
+
+    // exceeding the remaining allowance fails, and leaves the budget untouched
+    let err = budget.charge(&send(vec![coin(41, "uatom")])).unwrap_err();
+    assert!(err
+        .to_string()
+        .contains("insufficient uatom: available 40, needed 41"));
+    assert_eq!(budget.0, Coins::from_str("40uatom").unwrap());
+
+    // denoms not in the budget can't be spent
+    let err = budget.charge(&send(vec![coin(1, "umars")])).unwrap_err();
+    assert!(err
+        .to_string()
+        .contains("insufficient umars: available 0, needed 1"));
+
+    budget.charge(&send(vec![coin(40, "uatom")])).unwrap();
+    assert!(budget.0.is_empty());
+}
+
+#[test]
+fn charging_spend_budget_for_uninspectable_msgs() {
+    let mut budget = SpendBudget(Coins::from_str("100uatom").unwrap());
+
+    let msgs: Vec<CosmosMsg<Empty>> = vec![
+        CosmosMsg::Bank(BankMsg::Burn {
+            amount: vec![coin(10, "uatom")],
+        }),
+        CosmosMsg::Custom(Empty {}),
+    ];
+
+    // rejected unless explicitly allowed, leaving the budget untouched
+    let err = budget.charge(&msgs).unwrap_err();
+    assert!(err
+        .to_string()
+        .contains("cannot inspect the funds sent by a custom message"));
+    budget
+        .charge_allowing(&msgs, |msg| matches!(msg, CosmosMsg::Bank(_)))
+        .unwrap_err();
+    assert_eq!(budget.0, Coins::from_str("100uatom").unwrap());
+
+    // allowed messages are charged nothing, while the rest still count
+    budget
+        .charge_allowing(&msgs, |msg| matches!(msg, CosmosMsg::Custom(_)))
+        .unwrap();
+    assert_eq!(budget.0, Coins::from_str("90uatom").unwrap());
+}
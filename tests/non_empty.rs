@@ -0,0 +1,26 @@
+use std::str::FromStr;
+
+use cw_coins::{Coins, NonEmptyCoins};
+
+#[test]
+fn constructing() {
+    let coins = Coins::from_str("100uatom,0uosmo").unwrap();
+    let non_empty = NonEmptyCoins::try_from(coins.clone()).unwrap();
+    assert_eq!(*non_empty, coins);
+    assert_eq!(Coins::from(non_empty), coins);
+
+    let err = NonEmptyCoins::new(Coins::from_str("0uatom,0uosmo").unwrap()).unwrap_err();
+    assert!(err.to_string().contains("at least one non-zero amount"));
+    assert!(NonEmptyCoins::new(Coins::default()).is_err());
+}
+
+#[test]
+fn serde() {
+    let non_empty: NonEmptyCoins = serde_json::from_str(r#"{"uatom":"100"}"#).unwrap();
+    assert_eq!(non_empty.to_string(), "100uatom");
+    assert_eq!(serde_json::to_string(&non_empty).unwrap(), r#"{"uatom":"100"}"#);
+
+    let err = serde_json::from_str::<NonEmptyCoins>(r#"{"uatom":"0"}"#).unwrap_err();
+    assert!(err.to_string().contains("at least one non-zero amount"));
+    assert!(serde_json::from_str::<NonEmptyCoins>("{}").is_err());
+}
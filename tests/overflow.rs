@@ -0,0 +1,180 @@
+//! Exercises Coins arithmetic on boundary amounts, comparing every result against a reference
+//! computed in 256 bits. Run with `--features shadow-math` to additionally have every intermediate
+//! operation inside the library shadow-computed.
+use cosmwasm_std::{Decimal, Uint128, Uint256, Uint512};
+use cw_coins::Coins;
+
+const BOUNDARIES: [u128; 8] =
+    [0, 1, 2, u64::MAX as u128, u64::MAX as u128 + 1, u128::MAX / 2, u128::MAX - 1, u128::MAX];
+
+fn decimals() -> [Decimal; 5] {
+    [Decimal::zero(), Decimal::percent(50), Decimal::one(), Decimal::percent(150), Decimal::MAX]
+}
+
+fn narrow(wide: Uint256) -> Option<Uint128> {
+    Uint128::try_from(wide).ok()
+}
+
+fn one(amount: u128) -> Coins {
+    Coins::one("uatom", amount)
+}
+
+fn amount_of(coins: Coins) -> Uint128 {
+    coins.get("uatom").copied().unwrap_or_default()
+}
+
+#[test]
+fn adding_on_boundaries() {
+    for a in BOUNDARIES {
+        for b in BOUNDARIES {
+            let result = one(a).checked_add(&one(b)).ok().map(amount_of);
+            let expected = narrow(Uint256::from(a) + Uint256::from(b));
+            assert_eq!(result, expected, "{} + {}", a, b);
+        }
+    }
+}
+
+#[test]
+fn subtracting_on_boundaries() {
+    for a in BOUNDARIES {
+        for b in BOUNDARIES {
+            let result = one(a).checked_sub(&one(b)).ok().map(amount_of);
+            let expected = Uint256::from(a)
+                .checked_sub(Uint256::from(b))
+                .ok()
+                .and_then(narrow);
+            assert_eq!(result, expected, "{} - {}", a, b);
+        }
+    }
+}
+
+#[test]
+fn multiplying_on_boundaries() {
+    for a in BOUNDARIES {
+        for b in BOUNDARIES {
+            let result = one(a).checked_mul(Uint128::new(b)).ok().map(amount_of);
+            let expected = narrow(Uint256::from(a) * Uint256::from(b));
+            assert_eq!(result, expected, "{} * {}", a, b);
+        }
+    }
+}
+
+#[test]
+fn multiplying_by_ratios_on_boundaries() {
+    for a in BOUNDARIES {
+        for n in BOUNDARIES {
+            for d in BOUNDARIES {
+                let result = one(a)
+                    .checked_mul_ratio(Uint128::new(n), Uint128::new(d))
+                    .ok()
+                    .map(amount_of);
+                let expected = (Uint256::from(a) * Uint256::from(n))
+                    .checked_div(Uint256::from(d))
+                    .ok()
+                    .and_then(narrow);
+                assert_eq!(result, expected, "{} * {} / {}", a, n, d);
+            }
+        }
+    }
+}
+
+#[test]
+fn subtracting_without_underflow_on_boundaries() {
+    for a in BOUNDARIES {
+        for b in BOUNDARIES {
+            let (wide_a, wide_b) = (Uint256::from(a), Uint256::from(b));
+            let diff = narrow(wide_a.saturating_sub(wide_b)).unwrap();
+            let short = narrow(wide_b.saturating_sub(wide_a)).unwrap();
+
+            assert_eq!(amount_of(one(a).saturating_sub(&one(b))), diff, "{} - {}", a, b);
+            let (left, remainder) = one(a).sub_with_remainder(&one(b));
+            assert_eq!((amount_of(left), amount_of(remainder)), (diff, short), "{} - {}", a, b);
+            assert_eq!(amount_of(one(a).abs_diff(&one(b))), diff.max(short), "|{} - {}|", a, b);
+        }
+    }
+}
+
+#[test]
+fn dividing_on_boundaries() {
+    for a in BOUNDARIES {
+        for d in BOUNDARIES {
+            let result = one(a)
+                .checked_div(d)
+                .ok()
+                .map(|(q, r)| (amount_of(q), amount_of(r)));
+            let expected = (d != 0).then(|| (Uint128::new(a / d), Uint128::new(a % d)));
+            assert_eq!(result, expected, "{} / {}", a, d);
+        }
+    }
+}
+
+#[test]
+fn multiplying_by_decimals_on_boundaries() {
+    let unit = Uint512::from(Decimal::one().atomics());
+    for a in BOUNDARIES {
+        for ratio in decimals() {
+            let product = Uint512::from(a) * Uint512::from(ratio.atomics());
+            let floor = product / unit;
+            let ceil = floor + Uint512::from(u8::from(!(product % unit).is_zero()));
+            let narrow = |wide: Uint512| Uint128::try_from(Uint256::try_from(wide).ok()?).ok();
+
+            let result = one(a).mul_floor(ratio).ok().map(amount_of);
+            assert_eq!(result, narrow(floor), "{} * {} rounded down", a, ratio);
+            let result = one(a).mul_ceil(ratio).ok().map(amount_of);
+            assert_eq!(result, narrow(ceil), "{} * {} rounded up", a, ratio);
+        }
+    }
+}
+
+#[test]
+fn multiplying_by_wide_ratios_on_boundaries() {
+    let wide = [Uint256::zero(), Uint256::one(), Uint256::from(u128::MAX), Uint256::MAX];
+    for a in BOUNDARIES {
+        for n in wide {
+            for d in wide {
+                let result = one(a).checked_mul_wide(n, d).ok().map(amount_of);
+                let expected = Uint256::from(a)
+                    .checked_mul(n)
+                    .ok()
+                    .and_then(|product| product.checked_div(d).ok())
+                    .and_then(narrow);
+                assert_eq!(result, expected, "{} * {} / {}", a, n, d);
+            }
+        }
+    }
+}
+
+#[test]
+fn splitting_by_weights_on_boundaries() {
+    for a in BOUNDARIES {
+        for weights in [
+            vec![Decimal::one()],
+            vec![Decimal::one(), Decimal::one(), Decimal::one()],
+            vec![Decimal::MAX, Decimal::percent(1), Decimal::zero()],
+        ] {
+            let parts = one(a).split_weighted(&weights).unwrap();
+            let sum = parts.into_iter().sum::<Coins>();
+            assert_eq!(amount_of(sum), Uint128::new(a), "{} split by {:?}", a, weights);
+        }
+    }
+}
+
+#[test]
+fn interpolating_on_boundaries() {
+    let unit = Uint256::from(Decimal::one().atomics());
+    for a in BOUNDARIES {
+        for b in BOUNDARIES {
+            for t in decimals().into_iter().filter(|t| *t <= Decimal::one()) {
+                let result = amount_of(Coins::lerp(&one(a), &one(b), t).unwrap());
+                let (from, to) = (Uint256::from(a), Uint256::from(b));
+                let t_wide = Uint256::from(t.atomics());
+                let expected = if to >= from {
+                    from + (to - from) * t_wide / unit
+                } else {
+                    from - (from - to) * t_wide / unit
+                };
+                assert_eq!(Some(result), narrow(expected), "lerp({}, {}, {})", a, b, t);
+            }
+        }
+    }
+}
@@ -0,0 +1,64 @@
+use cosmwasm_std::{from_json, Decimal};
+use cw_coins::PerDenom;
+
+#[test]
+fn serde() {
+    let s = r#"{"uosmo":"0.5","factory/osmo1234abcd/subdenom":"0.8"}"#;
+
+    let factors: PerDenom<Decimal> = serde_json::from_str(s).unwrap();
+    assert_eq!(factors.len(), 2);
+    assert_eq!(factors.get("uosmo"), Some(&Decimal::percent(50)));
+
+    // keys are serialized in order
+    assert_eq!(
+        serde_json::to_string(&factors).unwrap(),
+        r#"{"factory/osmo1234abcd/subdenom":"0.8","uosmo":"0.5"}"#
+    );
+    let denoms = factors
+        .iter()
+        .map(|(denom, _)| denom.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(denoms, vec!["factory/osmo1234abcd/subdenom", "uosmo"]);
+
+    // the same goes for the JSON deserializer contracts use
+    assert_eq!(from_json::<PerDenom<Decimal>>(s).unwrap(), factors);
+    let caps: PerDenom<u64> = from_json(r#"{"uatom":100}"#).unwrap();
+    assert_eq!(caps.get("uatom"), Some(&100));
+
+    let err = from_json::<PerDenom<Decimal>>(r#"["uosmo"]"#).unwrap_err();
+    assert!(err
+        .to_string()
+        .contains("a map with non-duplicating, valid denoms as keys"));
+}
+
+#[test]
+fn handling_duplicates_and_invalid_denoms() {
+    let s = r#"{"uosmo":"0.5","uatom":"0.6","uosmo":"0.8"}"#;
+    let err = serde_json::from_str::<PerDenom<Decimal>>(s).unwrap_err();
+    assert!(err.to_string().contains("duplicate denom: uosmo"));
+
+    let s = r#"{"1uosmo":"0.5"}"#;
+    let err = serde_json::from_str::<PerDenom<Decimal>>(s).unwrap_err();
+    assert!(err.to_string().contains("invalid denom `1uosmo`"));
+
+    let s = r#"{"uosmo":"0.5","uosmo":"0.8"}"#;
+    let err = from_json::<PerDenom<Decimal>>(s).unwrap_err();
+    assert!(err.to_string().contains("duplicate denom: uosmo"));
+
+    let mut factors = PerDenom::new();
+    factors.insert("ab", Decimal::one()).unwrap_err();
+    factors.insert("u atom", Decimal::one()).unwrap_err();
+    assert!(factors.is_empty());
+}
+
+#[test]
+fn lookups_with_default() {
+    let mut caps = PerDenom::<u64>::new();
+    assert_eq!(caps.insert("uatom", 100).unwrap(), None);
+    assert_eq!(caps.insert("uatom", 200).unwrap(), Some(100));
+
+    assert_eq!(caps.get_or("uatom", &5), &200);
+    assert_eq!(caps.get_or("uosmo", &5), &5);
+    assert_eq!(caps.get_or_default("uatom"), 200);
+    assert_eq!(caps.get_or_default("uosmo"), 0);
+}
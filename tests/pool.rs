@@ -0,0 +1,110 @@
+#![cfg(feature = "unstable")]
+
+use std::str::FromStr;
+
+use cosmwasm_std::{from_json, to_json_vec, Addr, Uint128};
+use cw_coins::{Coins, CoinsError, PooledCoins};
+
+#[test]
+fn depositing_and_withdrawing() {
+    let alice = Addr::unchecked("alice");
+    let bob = Addr::unchecked("bob");
+
+    let mut pool = PooledCoins::default();
+
+    // first deposit mints shares equal to the largest amount
+    let shares = pool
+        .deposit(&alice, &Coins::from_str("100uatom,300uosmo").unwrap())
+        .unwrap();
+    assert_eq!(shares, Uint128::new(300));
+
+    // second deposit mints pro-rata to the least represented denom
+    let shares = pool
+        .deposit(&bob, &Coins::from_str("50uatom,200uosmo").unwrap())
+        .unwrap();
+    assert_eq!(shares, Uint128::new(150));
+    assert_eq!(pool.total(), &Coins::from_str("150uatom,500uosmo").unwrap());
+    assert_eq!(pool.total_shares(), Uint128::new(450));
+
+    // a deposit missing one of the pool's denoms mints nothing
+    let err = pool
+        .deposit(&bob, &Coins::from_str("50uatom").unwrap())
+        .unwrap_err();
+    assert_eq!(err, CoinsError::DepositTooSmall);
+
+    // cannot withdraw more shares than owned
+    let err = pool.withdraw(&bob, Uint128::new(151)).unwrap_err();
+    assert_eq!(
+        err,
+        CoinsError::InsufficientShares {
+            owner: "bob".to_string(),
+            needed: Uint128::new(151),
+            available: Uint128::new(150),
+        }
+    );
+
+    // partial withdrawal rounds down
+    let coins = pool.withdraw(&bob, Uint128::new(100)).unwrap();
+    assert_eq!(coins, Coins::from_str("33uatom,111uosmo").unwrap());
+
+    let coins = pool.withdraw(&bob, Uint128::new(50)).unwrap();
+    assert_eq!(coins, Coins::from_str("16uatom,55uosmo").unwrap());
+    assert_eq!(pool.shares_of(&bob), Uint128::zero());
+    assert!(!pool.shares().contains_key(&bob));
+
+    // the last withdrawal takes everything, including the dust
+    let coins = pool.withdraw(&alice, Uint128::new(300)).unwrap();
+    assert_eq!(coins, Coins::from_str("101uatom,334uosmo").unwrap());
+    assert_eq!(pool, PooledCoins::default());
+}
+
+#[test]
+fn conserving_dust_across_owners() {
+    let owners = ["alice", "bob", "carol"].map(Addr::unchecked);
+    let deposits =
+        ["7uatom,11uosmo", "5uatom,8uosmo", "3uatom,5uosmo"].map(|s| Coins::from_str(s).unwrap());
+
+    let mut pool = PooledCoins::default();
+    for (owner, coins) in owners.iter().zip(&deposits) {
+        pool.deposit(owner, coins).unwrap();
+    }
+    let deposited = deposits.iter().sum::<Coins>();
+    assert!(deposited.checked_sub(pool.total()).unwrap().is_empty());
+
+    // withdrawing one share at a time never pays out more than withdrawing them all at once
+    let mut paid = Coins::default();
+    let bob_shares = pool.shares_of(&owners[1]);
+    let at_once = pool.clone().withdraw(&owners[1], bob_shares).unwrap();
+    for _ in 0..bob_shares.u128() {
+        paid += pool.withdraw(&owners[1], Uint128::one()).unwrap();
+    }
+    assert!(at_once.checked_sub(&paid).is_ok(), "{} > {}", paid, at_once);
+
+    // the dust stays with the remaining owners, and the last of them takes whatever is left
+    for owner in [&owners[0], &owners[2]] {
+        let shares = pool.shares_of(owner);
+        paid += pool.withdraw(owner, shares).unwrap();
+        assert_eq!(paid.checked_add(pool.total()).unwrap(), deposited);
+    }
+    assert_eq!(paid, deposited);
+    assert_eq!(pool, PooledCoins::default());
+}
+
+#[test]
+fn serializing_pool() {
+    let alice = Addr::unchecked("alice");
+    let bob = Addr::unchecked("bob");
+
+    let mut pool = PooledCoins::default();
+    pool.deposit(&alice, &Coins::from_str("100uatom,300uosmo").unwrap())
+        .unwrap();
+    pool.deposit(&bob, &Coins::from_str("50uatom,150uosmo").unwrap())
+        .unwrap();
+
+    let bytes = to_json_vec(&pool).unwrap();
+    assert_eq!(
+        String::from_utf8(bytes.clone()).unwrap(),
+        r#"{"total":{"uatom":"150","uosmo":"450"},"total_shares":"450","shares":{"alice":"300","bob":"150"}}"#,
+    );
+    assert_eq!(from_json::<PooledCoins>(&bytes).unwrap(), pool);
+}
@@ -0,0 +1,16 @@
+use cosmwasm_std::coin;
+use cosmwasm_std::testing::mock_info;
+use cw_coins::prelude::*;
+
+#[test]
+fn converting_to_coins() {
+    let funds = vec![coin(100, "uatom"), coin(50, "uosmo")];
+
+    assert_eq!(funds.to_coins().unwrap().to_string(), "100uatom,50uosmo");
+    assert_eq!(funds[..1].to_coins().unwrap().to_string(), "100uatom");
+    assert_eq!(mock_info("sender", &funds).to_coins().unwrap().to_string(), "100uatom,50uosmo");
+
+    let funds = vec![coin(100, "uatom"), coin(50, "uatom")];
+    assert!(funds.to_coins().is_err());
+    assert!(mock_info("sender", &funds).to_coins().is_err());
+}
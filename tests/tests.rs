@@ -1,5 +1,10 @@
-use cosmwasm_std::coin;
-use cw_coins::Coins;
+use cosmwasm_std::{coin, Addr, StdError, Uint128, Uint64};
+use cw_coins::{
+    normalize_ibc_denom, validate_denom, validate_denom_strict, validate_factory_denom,
+    validate_ibc_denom, Coins, CoinsDelta, CoinsError, Denom, DenomValidation, InsufficientFunds,
+    ParseOptions, Shortfall, ZeroPolicy,
+};
+use std::collections::{BTreeMap, HashSet};
 use std::str::FromStr;
 
 #[test]
@@ -11,15 +16,62 @@ fn casting_vec() {
     assert_eq!(Coins::try_from(vec.as_slice()).unwrap(), coins);
     // Vec<Coin> --> Coins
     assert_eq!(Coins::try_from(vec.clone()).unwrap(), coins);
+    // &Vec<Coin> --> Coins
+    assert_eq!(Coins::try_from(&vec).unwrap(), coins);
 
     helpers::sort_by_denom(&mut vec);
 
     // &Coins --> Vec<Coins>
     // NOTE: the returned vec should be sorted
-    assert_eq!((&coins).to_vec(), vec);
+    assert_eq!(coins.to_vec(), vec);
     // Coins --> Vec<Coins>
     // NOTE: the returned vec should be sorted
-    assert_eq!(coins.into_vec(), vec);
+    assert_eq!(coins.clone().into_vec(), vec);
+    assert_eq!(Vec::from(coins.clone()), vec);
+
+    // [Coin; N] --> Coins
+    let array: [_; 3] = vec.clone().try_into().unwrap();
+    assert_eq!(Coins::try_from(array).unwrap(), coins);
+    let err = Coins::try_from([coin(1, "uatom"), coin(2, "uatom")]).unwrap_err();
+    assert!(err.to_string().contains("duplicate denom: uatom"));
+
+    // a single coin
+    assert_eq!(Coins::one("uatom", 12345u128).to_string(), "12345uatom");
+    assert_eq!(Coins::one("uatom".to_string(), Uint128::new(12345)).to_string(), "12345uatom");
+
+    // pairs --> Coins
+    let coins = Coins::from_pairs([("uatom", 12345u128), ("uosmo", 500)]).unwrap();
+    assert_eq!(coins.to_string(), "12345uatom,500uosmo");
+    let coins = Coins::from_pairs(vec![("uatom".to_string(), 12345u64)]).unwrap();
+    assert_eq!(coins.to_string(), "12345uatom");
+    let err = Coins::from_pairs([("uatom", 1u128), ("uatom", 2)]).unwrap_err();
+    assert!(err.to_string().contains("duplicate denom: uatom"));
+
+    // Coin --> Coins
+    assert_eq!(Coins::try_from(coin(12345, "uatom")).unwrap().to_string(), "12345uatom");
+    assert_eq!(Coins::try_from(&coin(12345, "uatom")).unwrap().to_string(), "12345uatom");
+    assert!(matches!(Coins::try_from(coin(1, "")).unwrap_err(), CoinsError::InvalidDenom { .. }));
+    assert!(Coins::try_from(coin(0, " ")).is_err());
+
+    // Coins --> BTreeMap
+    let map = coins.clone().into_inner();
+    assert_eq!(map.get("uatom"), Some(&Uint128::new(12345)));
+    assert_eq!(BTreeMap::from(coins.clone()), map);
+
+    // BTreeMap --> Coins
+    assert_eq!(Coins::try_from(map).unwrap(), coins);
+    let map = BTreeMap::from([("1uatom".to_string(), Uint128::new(1))]);
+    let err = Coins::try_from(map).unwrap_err();
+    assert!(err.to_string().contains("invalid denom `1uatom`"));
+}
+
+#[test]
+fn sorting_by_amount() {
+    let coins = Coins::from_str("50uosmo,100uatom,50ujuno,1umars").unwrap();
+    assert_eq!(
+        coins.to_vec_sorted_by_amount(),
+        vec![coin(100, "uatom"), coin(50, "ujuno"), coin(50, "uosmo"), coin(1, "umars")]
+    );
 }
 
 #[test]
@@ -39,6 +91,39 @@ fn casting_str() {
     // Coins --> String
     // NOTE: the generated string should be sorted
     assert_eq!(coins.to_string(), s2);
+
+    // TryFrom<&str> and TryFrom<String>
+    assert_eq!(Coins::try_from(s1).unwrap(), coins);
+    assert_eq!(Coins::try_from(s1.to_string()).unwrap(), coins);
+    assert!(Coins::try_from("12345").is_err());
+
+    // whitespace and a trailing comma are tolerated
+    let s3 = " 88888factory/osmo1234abcd/subdenom, 12345uatom ,\t69420ibc/1234ABCD,\n";
+    assert_eq!(Coins::from_str(s3).unwrap(), coins);
+
+    // empty coins round-trip through the empty string
+    assert_eq!(Coins::default().to_string(), "");
+    assert_eq!(Coins::from_str("").unwrap(), Coins::default());
+    assert_eq!(Coins::from_str("  ").unwrap(), Coins::default());
+
+    // amounts must consist of digits only, and denoms be valid
+    assert!(Coins::from_str("+12345uatom").is_err());
+    assert!(Coins::from_str("12.345uatom").is_err());
+    assert!(Coins::from_str("uatom").is_err());
+    assert!(Coins::from_str("12345").is_err());
+    assert!(Coins::from_str("12345u@tom").is_err());
+    assert!(Coins::from_str("12345ua").is_err());
+    assert!(Coins::from_str("12345\u{e9}atom").is_err());
+    // this is a valid SDK coin string, 12 of the denom `x34uatom`, as denoms may contain digits
+    // after the first letter
+    let coins = Coins::from_str("12x34uatom").unwrap();
+    assert_eq!(coins.get("x34uatom"), Some(&Uint128::new(12)));
+    assert_eq!(coins.to_string(), "12x34uatom");
+
+    // but not empty coins in the middle, or more than one trailing comma
+    assert!(Coins::from_str("12345uatom,,69420ibc/1234ABCD").is_err());
+    assert!(Coins::from_str("12345uatom,,").is_err());
+    assert!(Coins::from_str(",").is_err());
 }
 
 #[test]
@@ -81,14 +166,168 @@ fn handling_duplicates() {
     let s = "12345uatom,88888factory/osmo1234abcd/subdenom,67890uatom,69420ibc/1234ABCD";
 
     let err = Coins::from_str(s).unwrap_err();
-    assert!(err.to_string().contains("duplicate denoms"));
+    assert!(err.to_string().contains("duplicate denom: uatom"));
 
     // same with Vec<Coin>
     let mut vec = helpers::mock_vec();
     vec.push(coin(67890, "uatom"));
 
     let err = Coins::try_from(vec).unwrap_err();
-    assert!(err.to_string().contains("duplicate denoms"));
+    assert_eq!(
+        err,
+        CoinsError::DuplicateDenom {
+            denom: "uatom".to_string()
+        }
+    );
+
+    // converting into a `StdError` for use in contracts
+    let err = StdError::from(err);
+    assert!(matches!(err, StdError::ParseErr { .. }));
+    assert!(err.to_string().contains("duplicate denom: uatom"));
+}
+
+#[test]
+fn merging_duplicates() {
+    let funds = vec![coin(100, "uatom"), coin(0, "uosmo"), coin(23, "uatom"), coin(5, "umars")];
+    assert!(Coins::try_from(funds.clone()).is_err());
+    let coins = Coins::try_from_vec_merging(funds).unwrap();
+    assert_eq!(coins.to_string(), "123uatom,5umars");
+
+    let err =
+        Coins::try_from_vec_merging(vec![coin(u128::MAX, "uatom"), coin(1, "uatom")]).unwrap_err();
+    assert!(matches!(err, CoinsError::Overflow { .. }));
+
+    let funds = (0..257).map(|_| coin(1, "uatom")).collect::<Vec<_>>();
+    assert_eq!(
+        Coins::try_from_vec_merging(funds).unwrap_err(),
+        CoinsError::TooManyDenoms {
+            max: 256
+        }
+    );
+}
+
+#[test]
+fn rejecting_blank_denoms() {
+    for denom in ["", " ", "\t\n"] {
+        let blank = |err: CoinsError| matches!(err, CoinsError::InvalidDenom { .. });
+
+        // even when denoms are otherwise taken as they are
+        assert!(blank(Coins::try_from(vec![coin(1, denom)]).unwrap_err()), "{:?}", denom);
+        assert!(blank(Coins::try_from(vec![coin(0, denom)]).unwrap_err()), "{:?}", denom);
+        assert!(blank(Coins::try_from_vec_merging(vec![coin(1, denom)]).unwrap_err()));
+        assert!(blank(Coins::from_pairs([(denom, 1u128)]).unwrap_err()));
+        assert!(Coins::default().checked_add_coin(&coin(1, denom)).is_err());
+
+        let json = format!(r#"{{"{}":"1"}}"#, denom.escape_default());
+        let err = serde_json::from_str::<Coins>(&json).unwrap_err();
+        assert!(err.to_string().contains("must not be empty or whitespace"), "{:?}", denom);
+    }
+}
+
+#[test]
+fn denoms() {
+    let denom = Denom::from_str("uatom").unwrap();
+    assert_eq!(denom, "uatom");
+    assert_eq!(denom.to_string(), "uatom");
+    assert_eq!(String::from(denom.clone()), "uatom");
+    assert!(Denom::new("factory/osmo1abcd/uusdc").unwrap() < denom);
+
+    for blank in ["", " ", "\t"] {
+        assert!(Denom::new(blank).is_err(), "{:?}", blank);
+    }
+
+    // serialized as a plain string, and validated when deserialized
+    assert_eq!(serde_json::to_string(&denom).unwrap(), r#""uatom""#);
+    assert_eq!(serde_json::from_str::<Denom>(r#""uatom""#).unwrap(), denom);
+    assert!(serde_json::from_str::<Denom>(r#""  ""#).is_err());
+
+    // the keys of coins are denoms, and can be looked up with plain strings
+    let coins = Coins::from_str("12uosmo,34uatom").unwrap();
+    let first = coins.keys().next().unwrap();
+    assert_eq!(*first, denom);
+    assert_eq!(coins.get(denom.as_str()), Some(&Uint128::new(34)));
+}
+
+#[test]
+#[should_panic(expected = "must not be empty or whitespace")]
+fn setting_blank_denom_panics() {
+    Coins::default().with(" ", 1u128);
+}
+
+#[test]
+#[should_panic(expected = "must not be empty or whitespace")]
+fn collecting_blank_denom_panics() {
+    let _ = [(String::new(), Uint128::new(1))]
+        .into_iter()
+        .collect::<Coins>();
+}
+
+#[test]
+fn deserializing_array_form() {
+    let json = r#"[
+        {"denom": "uatom", "amount": "12345"},
+        {"denom": "uosmo", "amount": "0"},
+        {"denom": "ibc/1234ABCD", "amount": "69420"}
+    ]"#;
+    let expected = Coins::from_str("12345uatom,69420ibc/1234ABCD").unwrap();
+    assert_eq!(serde_json::from_str::<Coins>(json).unwrap(), expected);
+    assert_eq!(cosmwasm_std::from_json::<Coins>(json).unwrap(), expected);
+
+    // a drop-in replacement for `Vec<Coin>`
+    let funds = vec![coin(12345, "uatom"), coin(69420, "ibc/1234ABCD")];
+    let json = serde_json::to_string(&funds).unwrap();
+    assert_eq!(serde_json::from_str::<Coins>(&json).unwrap(), expected);
+    assert_eq!(serde_json::from_str::<Coins>("[]").unwrap(), Coins::default());
+
+    let json = r#"[{"denom": "uatom", "amount": "1"}, {"denom": "uatom", "amount": "2"}]"#;
+    let err = serde_json::from_str::<Coins>(json).unwrap_err();
+    assert!(err.to_string().contains("duplicate denom: uatom"));
+
+    let json = r#"[{"denom": "uatom", "amount": "+1"}]"#;
+    let err = serde_json::from_str::<Coins>(json).unwrap_err();
+    assert!(err.to_string().contains("invalid amount: +1"));
+
+    // the same options apply as to the map form
+    let json = r#"[{"denom": "uatom", "amount": "0"}]"#;
+    let options = ParseOptions {
+        zeros: ZeroPolicy::Reject,
+        ..Default::default()
+    };
+    let mut de = serde_json::Deserializer::from_str(json);
+    assert!(Coins::deserialize_with_options(&mut de, &options).is_err());
+
+    // neither a map nor a list
+    assert!(serde_json::from_str::<Coins>(r#""12345uatom""#).is_err());
+}
+
+#[test]
+fn deserializing_single_coin() {
+    let expected = Coins::one("uatom", 123u128);
+    for json in [r#"{"denom": "uatom", "amount": "123"}"#, r#"{"amount": "123", "denom": "uatom"}"#]
+    {
+        assert_eq!(serde_json::from_str::<Coins>(json).unwrap(), expected);
+        assert_eq!(cosmwasm_std::from_json::<Coins>(json).unwrap(), expected);
+    }
+    let json = serde_json::to_string(&coin(123, "uatom")).unwrap();
+    assert_eq!(serde_json::from_str::<Coins>(&json).unwrap(), expected);
+
+    // a zero coin is left out, the same way as in the other forms
+    let json = r#"{"denom": "uatom", "amount": "0"}"#;
+    assert!(serde_json::from_str::<Coins>(json).unwrap().is_empty());
+
+    // `denom` and `amount` are valid denoms too, so the map form takes precedence where the value
+    // of `denom` is an amount
+    let json = r#"{"denom": "1", "amount": "2"}"#;
+    assert_eq!(serde_json::from_str::<Coins>(json).unwrap().to_string(), "2amount,1denom");
+    let json = r#"{"denom": "1", "amount": "2", "uatom": "3"}"#;
+    assert_eq!(serde_json::from_str::<Coins>(json).unwrap().len(), 3);
+    assert_eq!(cosmwasm_std::from_json::<Coins>(json).unwrap().len(), 3);
+
+    let json = r#"{"denom": "uatom", "amount": "12.3"}"#;
+    let err = serde_json::from_str::<Coins>(json).unwrap_err();
+    assert!(err.to_string().contains("invalid amount: 12.3"));
+    let json = r#"{"denom": "uatom", "amount": "1", "uosmo": "2"}"#;
+    assert!(serde_json::from_str::<Coins>(json).is_err());
 }
 
 #[test]
@@ -102,17 +341,585 @@ fn handling_invalid_amount() {
 
     let err = serde_json::from_str::<Coins>(s).unwrap_err();
     assert!(err.to_string().contains("invalid amount: ngmi"));
+
+    // JSON and plain strings accept the same amounts
+    for amount in ["", "+5", "-5", "5.0", "340282366920938463463374607431768211456"] {
+        let json = format!(r#"{{"uatom":"{}"}}"#, amount);
+        assert!(serde_json::from_str::<Coins>(&json).is_err(), "{}", amount);
+        assert!(Coins::from_str(&format!("{}uatom", amount)).is_err(), "{}", amount);
+    }
+    for amount in ["0", "007", "340282366920938463463374607431768211455"] {
+        let json = format!(r#"{{"uatom":"{}"}}"#, amount);
+        assert_eq!(
+            serde_json::from_str::<Coins>(&json).unwrap(),
+            Coins::from_str(&format!("{}uatom", amount)).unwrap()
+        );
+    }
+}
+
+#[test]
+fn handling_invalid_denoms() {
+    for denom in
+        ["uatom", "factory/osmo1234abcd/subdenom", "ibc/1234ABCD", "a:b.c_d-e", &"a".repeat(128)]
+    {
+        validate_denom(denom).unwrap();
+    }
+    for denom in ["", "ua", "1uatom", "/uatom", "u atom", "uatom!", "uåtom", &"a".repeat(129)] {
+        assert!(validate_denom(denom).is_err(), "{}", denom);
+    }
+
+    // denoms are only validated when converting vectors in strict mode
+    let funds = vec![coin(100, "uatom"), coin(1, "u$d")];
+    assert_eq!(Coins::from_vec(funds.clone(), false).unwrap().len(), 2);
+    let err = Coins::from_vec(funds, true).unwrap_err();
+    assert!(err.to_string().contains("invalid denom `u$d`"));
+    Coins::from_vec(vec![coin(100, "uatom")], true).unwrap();
+}
+
+#[test]
+fn handling_invalid_ibc_denoms() {
+    let atom = "ibc/27394FB092D2ECCD56123C74F36E4C1F926001CEADA9CA97EA622B25F41E5EB2";
+    validate_ibc_denom(atom).unwrap();
+    validate_ibc_denom("uatom").unwrap();
+
+    let typos = [
+        "ibc/27394fb092d2eccd56123c74f36e4c1f926001ceada9ca97ea622b25f41e5eb2",
+        "ibc/27394FB092D2ECCD56123C74F36E4C1F926001CEADA9CA97EA622B25F41E5EB",
+        "ibc/27394FB092D2ECCD56123C74F36E4C1F926001CEADA9CA97EA622B25F41E5EB22",
+        "ibc/27394FB092D2ECCD56123C74F36E4C1F926001CEADA9CA97EA622B25F41E5EBG",
+    ];
+    for denom in typos {
+        assert!(validate_ibc_denom(denom).is_err(), "{}", denom);
+        // valid according to the SDK's rules, so only caught in strict mode
+        validate_denom(denom).unwrap();
+        assert!(validate_denom_strict(denom).is_err(), "{}", denom);
+    }
+
+    let s = format!("100{},1{}", atom, typos[0]);
+    assert_eq!(Coins::from_str(&s).unwrap().len(), 2);
+    let err = Coins::from_str_strict(&s).unwrap_err();
+    assert!(err
+        .to_string()
+        .contains("64 uppercase hexadecimal characters"));
+    assert_eq!(
+        Coins::from_str_strict(&format!("100{}", atom))
+            .unwrap()
+            .len(),
+        1
+    );
+
+    assert!(Coins::from_vec(vec![coin(1, typos[1])], false).is_ok());
+    assert!(Coins::from_vec(vec![coin(1, typos[1])], true).is_err());
+}
+
+#[test]
+fn handling_invalid_factory_denoms() {
+    let creator = "osmo1wd8rd8yp7l5mykgqsmvm5nshhrc2zrcq5w2cm8";
+    for denom in [
+        format!("factory/{}/uusdc", creator),
+        format!("factory/{}/a", creator),
+        format!("factory/{}/nested/sub.denom_1-2:3", creator),
+        format!("factory/{}/{}", creator, "a".repeat(44)),
+        "uatom".to_string(),
+    ] {
+        validate_factory_denom(&denom).unwrap();
+        validate_denom_strict(&denom).unwrap();
+    }
+
+    for (denom, reason) in [
+        ("factory/uusdc".to_string(), "must be of the form"),
+        ("factory/osmo1abcd/uusdc".to_string(), "creator must be a bech32 address"),
+        ("factory/Osmo1wd8rd8yp7l5m/uusdc".to_string(), "creator must be a bech32 address"),
+        ("factory/1wd8rd8yp7l5m/uusdc".to_string(), "creator must be a bech32 address"),
+        ("factory//uusdc".to_string(), "creator must be a bech32 address"),
+        (format!("factory/osmo1{}/uusdc", "q".repeat(71)), "creator must be a bech32 address"),
+        (format!("factory/{}/", creator), "between 1 and 44 characters"),
+        (format!("factory/{}/{}", creator, "a".repeat(45)), "between 1 and 44 characters"),
+        (format!("factory/{}/u$d", creator), "characters other than"),
+    ] {
+        let err = validate_factory_denom(&denom).unwrap_err();
+        assert!(err.to_string().contains(reason), "{}: {}", denom, err);
+        assert!(validate_denom_strict(&denom).is_err(), "{}", denom);
+    }
+}
+
+#[test]
+fn iteration_order() {
+    // inserted in an arbitrary order; note that uppercase letters sort before lowercase ones
+    let coins = Coins::from_str("1uosmo,2ibc/ABCD,3uatom,4factory/osmo1/x,5Zeta").unwrap();
+    let expected = ["Zeta", "factory/osmo1/x", "ibc/ABCD", "uatom", "uosmo"];
+
+    let denoms = coins
+        .to_vec()
+        .into_iter()
+        .map(|coin| coin.denom)
+        .collect::<Vec<_>>();
+    assert_eq!(denoms, expected);
+
+    let denoms = coins
+        .clone()
+        .into_vec()
+        .into_iter()
+        .map(|coin| coin.denom)
+        .collect::<Vec<_>>();
+    assert_eq!(denoms, expected);
+
+    let denoms = coins
+        .clone()
+        .into_iter()
+        .map(|coin| coin.denom)
+        .collect::<Vec<_>>();
+    assert_eq!(denoms, expected);
+
+    let denoms = (&coins)
+        .into_iter()
+        .map(|(denom, _)| denom)
+        .collect::<Vec<_>>();
+    assert_eq!(denoms, expected);
+
+    let denoms = coins.iter().map(|coin| coin.denom).collect::<Vec<_>>();
+    assert_eq!(denoms, expected);
+
+    assert_eq!(coins.to_string(), "5Zeta,4factory/osmo1/x,2ibc/ABCD,3uatom,1uosmo");
+}
+
+#[test]
+fn coins_macro() {
+    let denom = "uosmo".to_string();
+    let coins = cw_coins::coins! {
+        "uatom" => 12345u128,
+        denom => 500,
+        "umars" => Uint128::new(1),
+    };
+    assert_eq!(coins.to_string(), "12345uatom,1umars,500uosmo");
+
+    // any unsigned integer type is accepted, and unsuffixed literals aren't limited to 32 bits
+    let amount = 7u64;
+    let coins = cw_coins::coins! {
+        "uatom" => amount,
+        "ujuno" => 3u32,
+        "umars" => Uint64::new(2),
+        "uosmo" => 1_000_000_000_000_000,
+        "uusdc" => 5u8
+    };
+    assert_eq!(coins.to_string(), "7uatom,3ujuno,2umars,1000000000000000uosmo,5uusdc");
+
+    assert!(cw_coins::coins! {}.is_empty());
+}
+
+#[test]
+#[should_panic(expected = "duplicate denom: uatom")]
+fn coins_macro_duplicates_panic() {
+    let _ = cw_coins::coins! { "uatom" => 1, "uatom" => 2 };
+}
+
+#[test]
+fn const_construction() {
+    const EMPTY: Coins = Coins::EMPTY;
+    const NEW: Coins = Coins::new();
+
+    assert_eq!(EMPTY, Coins::default());
+    assert_eq!(NEW, Coins::default());
+}
+
+#[test]
+fn with_and_without() {
+    let coins = Coins::new()
+        .with("uatom", 100u128)
+        .with("uosmo", Uint128::new(50))
+        .with("umars", 1u64)
+        .without("umars");
+    assert_eq!(coins.to_string(), "100uatom,50uosmo");
+
+    // replacing an amount, and zeroing one
+    let coins = coins.with("uatom", 5u128).with("uosmo", 0u128);
+    assert_eq!(coins.to_string(), "5uatom");
+
+    assert!(coins.without("uatom").without("uosmo").is_empty());
+}
+
+#[test]
+fn hashing() {
+    let mut set = HashSet::new();
+    assert!(set.insert(Coins::from_str("1uatom,2uosmo").unwrap()));
+    assert!(set.insert(Coins::from_str("1uatom").unwrap()));
+    assert!(!set.insert(Coins::from_str("2uosmo,1uatom").unwrap()));
+    assert_eq!(set.len(), 2);
+}
+
+#[test]
+fn reading_the_map() {
+    let coins = Coins::from_str("12uosmo,34uatom").unwrap();
+
+    assert_eq!(coins.get("uatom"), Some(&Uint128::new(34)));
+    assert!(coins.contains_key("uosmo"));
+    assert_eq!(coins.keys().collect::<Vec<_>>(), ["uatom", "uosmo"]);
+
+    let map: &BTreeMap<Denom, Uint128> = coins.as_ref();
+    assert_eq!(map.len(), 2);
+}
+
+#[test]
+fn keys_fingerprint() {
+    let a = Coins::from_str("100uatom,50uosmo").unwrap();
+    let b = Coins::from_str("1uosmo,2uatom").unwrap();
+    let c = Coins::from_str("100uatom,50uosmo,1umars").unwrap();
+
+    // same denoms, different amounts
+    assert_eq!(a.keys_fingerprint(), b.keys_fingerprint());
+    assert_ne!(a.keys_fingerprint(), c.keys_fingerprint());
+
+    // denoms are length-prefixed, so concatenations don't collide
+    let d = Coins::from_str("1uat,1omuosmo").unwrap();
+    assert_ne!(a.keys_fingerprint(), d.keys_fingerprint());
+
+    // the fingerprint is stable
+    assert_eq!(Coins::default().keys_fingerprint(), 0xcbf29ce484222325);
 }
 
 #[test]
 fn length() {
     let coins = Coins::default();
     assert_eq!(coins.len(), 0);
-    assert_eq!(coins.is_empty(), true);
+    assert!(coins.is_empty());
 
     let coins = helpers::mock_coins();
     assert_eq!(coins.len(), 3);
-    assert_eq!(coins.is_empty(), false);
+    assert!(!coins.is_empty());
+}
+
+#[test]
+fn union() {
+    let a = Coins::from_str("100uatom,50uosmo").unwrap();
+    let b = Coins::from_str("30uatom,80uosmo,25umars").unwrap();
+
+    let expected = Coins::from_str("100uatom,25umars,80uosmo").unwrap();
+    assert_eq!(a.union(&b), expected);
+    assert_eq!(b.union(&a), expected);
+
+    // union with an empty collection is the identity
+    assert_eq!(a.union(&Coins::default()), a);
+}
+
+#[test]
+fn intersection() {
+    let a = Coins::from_str("100uatom,50uosmo").unwrap();
+    let b = Coins::from_str("30uatom,80uosmo,25umars").unwrap();
+
+    let expected = Coins::from_str("30uatom,50uosmo").unwrap();
+    assert_eq!(a.intersection(&b), expected);
+    assert_eq!(b.intersection(&a), expected);
+
+    // intersection with an empty collection is empty
+    assert!(a.intersection(&Coins::default()).is_empty());
+}
+
+#[test]
+fn max_and_min_with() {
+    let a = Coins::from_str("100uatom,50uosmo,0uusdc").unwrap();
+    let b = Coins::from_str("30uatom,80uosmo,25umars").unwrap();
+
+    assert_eq!(a.clone().max_with(&b), Coins::from_str("100uatom,25umars,80uosmo,0uusdc").unwrap());
+    assert_eq!(b.clone().max_with(&a), Coins::from_str("100uatom,25umars,80uosmo,0uusdc").unwrap());
+
+    // denoms present on one side only, and zero minimums, are dropped
+    assert_eq!(a.clone().min_with(&b), Coins::from_str("30uatom,50uosmo").unwrap());
+    assert_eq!(b.min_with(&a), Coins::from_str("30uatom,50uosmo").unwrap());
+
+    assert!(a.min_with(&Coins::default()).is_empty());
+}
+
+#[test]
+fn common_denoms() {
+    let a = Coins::from_str("100uatom,50uosmo,7ujuno").unwrap();
+    let b = Coins::from_str("30uosmo,25umars,1ujuno").unwrap();
+
+    assert_eq!(a.common_denoms(&b), vec!["ujuno", "uosmo"]);
+    assert_eq!(b.common_denoms(&a), vec!["ujuno", "uosmo"]);
+    assert!(a.common_denoms(&Coins::default()).is_empty());
+}
+
+#[test]
+fn symmetric_difference() {
+    let expected = Coins::from_str("100ujuno,25umars,50uosmo").unwrap();
+    let actual = Coins::from_str("30uatom,25umars").unwrap();
+    let expected_reserves = Coins::from_str("100uatom,50uosmo,100ujuno").unwrap();
+
+    assert_eq!(expected_reserves.symmetric_difference(&actual), expected);
+    assert_eq!(actual.symmetric_difference(&expected_reserves), expected);
+    assert!(actual.symmetric_difference(&actual).is_empty());
+}
+
+#[test]
+fn difference() {
+    let a = Coins::from_str("100uatom,50uosmo").unwrap();
+    let b = Coins::from_str("30uatom,25umars").unwrap();
+
+    assert_eq!(a.difference(&b), Coins::from_str("50uosmo").unwrap());
+    assert_eq!(b.difference(&a), Coins::from_str("25umars").unwrap());
+    assert_eq!(a.difference(&Coins::default()), a);
+    assert!(a.difference(&a).is_empty());
+}
+
+#[test]
+fn any_and_all() {
+    let coins = Coins::from_str("100uatom,50uosmo").unwrap();
+    let whitelist = ["uatom", "uosmo", "umars"];
+
+    assert!(coins.any(|_, amount| amount > Uint128::new(99)));
+    assert!(!coins.any(|_, amount| amount > Uint128::new(100)));
+    assert!(coins.all(|denom, _| whitelist.contains(&denom)));
+    assert!(!coins.all(|denom, _| denom == "uatom"));
+
+    assert!(!Coins::default().any(|_, _| true));
+    assert!(Coins::default().all(|_, _| false));
+}
+
+#[test]
+fn subset_and_superset() {
+    let required = Coins::from_str("100uatom,50uosmo").unwrap();
+    let available = Coins::from_str("100uatom,80uosmo,25umars").unwrap();
+
+    assert!(required.is_subset(&available));
+    assert!(available.is_superset(&required));
+    assert!(!available.is_subset(&required));
+    assert!(!required.is_superset(&available));
+
+    // a missing denom counts as zero
+    let required = Coins::from_str("1uusdc").unwrap();
+    assert!(!required.is_subset(&available));
+
+    // the empty collection is a subset of everything
+    assert!(Coins::default().is_subset(&available));
+    assert!(available.is_subset(&available));
+}
+
+#[test]
+fn equality_ignoring_zeros() {
+    let a = Coins::from_str("100uatom,50uosmo").unwrap();
+    let b = Coins::from_str("100uatom,0umars,50uosmo,0uusdc").unwrap();
+
+    // zero amounts are never stored in the first place, so plain equality disregards them
+    assert_eq!(a, b);
+    assert_eq!(Coins::from_str("0uatom").unwrap(), Coins::default());
+    assert_ne!(a, Coins::from_str("100uatom,51uosmo").unwrap());
+}
+
+#[test]
+fn strict_domination() {
+    let old_bid = Coins::from_str("100uatom,50uosmo").unwrap();
+
+    assert!(Coins::from_str("101uatom,51uosmo")
+        .unwrap()
+        .strictly_greater_than(&old_bid));
+    assert!(Coins::from_str("101uatom,51uosmo,1umars")
+        .unwrap()
+        .strictly_greater_than(&old_bid));
+    assert!(!Coins::from_str("101uatom,50uosmo")
+        .unwrap()
+        .strictly_greater_than(&old_bid));
+    assert!(!Coins::from_str("1000uatom")
+        .unwrap()
+        .strictly_greater_than(&old_bid));
+    assert!(!old_bid.strictly_greater_than(&old_bid));
+    assert!(old_bid.strictly_greater_than(&Coins::default()));
+}
+
+#[test]
+fn covering() {
+    let available = Coins::from_str("100uatom,50uosmo").unwrap();
+
+    available
+        .covers(&Coins::from_str("100uatom,10uosmo").unwrap())
+        .unwrap();
+    available.covers(&Coins::default()).unwrap();
+
+    let err = available
+        .covers(&Coins::from_str("150uatom,1umars,10uosmo").unwrap())
+        .unwrap_err();
+    assert_eq!(
+        err,
+        InsufficientFunds {
+            shortfalls: vec![
+                Shortfall {
+                    denom: "uatom".to_string(),
+                    required: Uint128::new(150),
+                    available: Uint128::new(100),
+                },
+                Shortfall {
+                    denom: "umars".to_string(),
+                    required: Uint128::new(1),
+                    available: Uint128::zero(),
+                },
+            ],
+        }
+    );
+    assert_eq!(
+        err.to_string(),
+        "insufficient funds: uatom (required 150, available 100), umars (required 1, available 0)"
+    );
+}
+
+#[test]
+fn fetching_from_denoms() {
+    let prices = [("uatom", 10u128), ("uosmo", 2)];
+    let fetch = |prices: &[(&str, u128); 2], denom: &str| {
+        prices
+            .iter()
+            .find(|(d, _)| *d == denom)
+            .map(|(_, price)| Uint128::new(*price))
+            .ok_or_else(|| cosmwasm_std::StdError::not_found(denom))
+    };
+
+    let coins = Coins::from_denoms_via(prices, ["uosmo", "uatom"], fetch).unwrap();
+    assert_eq!(coins, Coins::from_str("10uatom,2uosmo").unwrap());
+
+    // all failures are reported, not just the first one
+    let err = Coins::from_denoms_via(prices, ["umars", "uatom", "ujuno"], fetch).unwrap_err();
+    let denoms = err
+        .errors
+        .iter()
+        .map(|(denom, _)| denom.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(denoms, vec!["umars", "ujuno"]);
+    assert!(err.to_string().contains("umars: umars not found"));
+}
+
+#[test]
+fn merging_attributed() {
+    let alice = Addr::unchecked("alice");
+    let bob = Addr::unchecked("bob");
+
+    let (total, per_source) = Coins::merge_attributed(vec![
+        (alice.clone(), Coins::from_str("100uatom").unwrap()),
+        (bob.clone(), Coins::from_str("50uatom,20uosmo").unwrap()),
+        (alice.clone(), Coins::from_str("1uatom,5uosmo").unwrap()),
+    ])
+    .unwrap();
+
+    assert_eq!(total, Coins::from_str("151uatom,25uosmo").unwrap());
+    assert_eq!(per_source.len(), 2);
+    assert_eq!(per_source[&alice], Coins::from_str("101uatom,5uosmo").unwrap());
+    assert_eq!(per_source[&bob], Coins::from_str("50uatom,20uosmo").unwrap());
+
+    // overflowing the total is an error
+    let max = Coins::from_str(&format!("{}uatom", u128::MAX)).unwrap();
+    let one = Coins::from_str("1uatom").unwrap();
+    Coins::merge_attributed(vec![(alice, max), (bob, one)]).unwrap_err();
+}
+
+#[test]
+fn contains_at_least() {
+    let coins = helpers::mock_coins();
+
+    assert!(coins.contains_at_least(&coin(12345, "uatom")));
+    assert!(coins.contains_at_least(&coin(1, "uatom")));
+    assert!(!coins.contains_at_least(&coin(12346, "uatom")));
+    assert!(!coins.contains_at_least(&coin(1, "uosmo")));
+    assert!(coins.contains_at_least(&coin(0, "uosmo")));
+}
+
+#[test]
+fn compact_string() {
+    let coins =
+        Coins::from_str("1234567uatom,999umars,2000000uosmo,1500000000000000uusdc").unwrap();
+    assert_eq!(
+        coins.to_compact_string(false).unwrap(),
+        "1.2M uatom, 999 umars, 2M uosmo, 1.5e15 uusdc"
+    );
+
+    // rendering 1234567uatom as 1.2M is lossy, so strict mode refuses
+    let err = coins.to_compact_string(true).unwrap_err();
+    assert_eq!(
+        err,
+        CoinsError::LossyCompact {
+            denom: "uatom".to_string(),
+            amount: Uint128::new(1234567),
+        }
+    );
+
+    let coins = Coins::from_str("1200000uatom,999umars").unwrap();
+    assert_eq!(coins.to_compact_string(true).unwrap(), "1.2M uatom, 999 umars");
+
+    let coins = Coins::from_str("340282366920938463463374607431768211455uatom").unwrap();
+    assert_eq!(coins.to_compact_string(false).unwrap(), "340.2e36 uatom");
+
+    assert_eq!(Coins::default().to_compact_string(true).unwrap(), "");
+}
+
+#[test]
+fn partial_ordering() {
+    let a = Coins::from_str("100uatom,50uosmo").unwrap();
+    let b = Coins::from_str("100uatom,80uosmo,25umars").unwrap();
+    let c = Coins::from_str("200uatom,10uosmo").unwrap();
+
+    assert!(a <= b);
+    assert!(a < b);
+    assert!(b > a);
+    assert!(a <= a.clone());
+    assert!(Coins::default() < a);
+
+    // incomparable
+    assert_eq!(a.partial_cmp(&c), None);
+    assert_eq!(c.partial_cmp(&a), None);
+}
+
+#[test]
+fn upholding_invariants() {
+    let expected = Coins::from_str("100uatom").unwrap();
+
+    // every constructor leaves out zero amounts
+    assert_eq!(Coins::try_from(vec![coin(100, "uatom"), coin(0, "uosmo")]).unwrap(), expected);
+    assert_eq!(serde_json::from_str::<Coins>(r#"{"uatom":"100","uosmo":"0"}"#).unwrap(), expected);
+    assert_eq!(Coins::from_str("100uatom,0uosmo").unwrap(), expected);
+    assert_eq!(Coins::from_pairs([("uatom", 100u128), ("uosmo", 0)]).unwrap(), expected);
+    let map = BTreeMap::from([
+        ("uatom".to_string(), Uint128::new(100)),
+        ("uosmo".to_string(), Uint128::zero()),
+    ]);
+    assert_eq!(Coins::try_from(map).unwrap(), expected);
+    assert!(Coins::one("uatom", 0u128).is_empty());
+    assert!(Coins::try_from(coin(0, "uatom")).unwrap().is_empty());
+
+    // adding a zero coin is a no-op, whatever its denom
+    assert!((Coins::default() + coin(0, "")).is_empty());
+    assert!(Coins::default()
+        .checked_add_coin(&coin(0, " "))
+        .unwrap()
+        .is_empty());
+    assert!([coin(0, "")].into_iter().collect::<Coins>().is_empty());
+
+    // zero amounts still count towards duplicates
+    assert!(Coins::try_from(vec![coin(0, "uatom"), coin(100, "uatom")]).is_err());
+
+    // mutating through methods
+    let mut coins = expected.clone();
+    assert_eq!(coins.set("uosmo", 50u128), None);
+    assert_eq!(coins.set("uatom", 0u128), Some(Uint128::new(100)));
+    assert_eq!(coins.to_string(), "50uosmo");
+    assert_eq!(coins.remove("uosmo"), Some(Uint128::new(50)));
+    assert_eq!(coins.remove("uosmo"), None);
+    assert!(coins.is_empty());
+}
+
+#[test]
+fn diff() {
+    let before = Coins::from_str("100uatom,50uosmo,25umars,0uusdc,10ujuno").unwrap();
+    let after = Coins::from_str("80uatom,70uosmo,5uusdc,0ujuno,1uion").unwrap();
+
+    let delta = before.diff(&after);
+    assert_eq!(
+        delta,
+        CoinsDelta {
+            added: Coins::from_str("1uion,5uusdc").unwrap(),
+            removed: Coins::from_str("10ujuno,25umars").unwrap(),
+            increased: Coins::from_str("20uosmo").unwrap(),
+            decreased: Coins::from_str("20uatom").unwrap(),
+        }
+    );
+    assert!(!delta.is_empty());
+
+    assert!(before.diff(&before).is_empty());
 }
 
 mod helpers {
@@ -121,7 +928,7 @@ mod helpers {
     use std::collections::BTreeMap;
 
     /// Sort a Vec<Coin> by denom alphabetically
-    pub(super) fn sort_by_denom(vec: &mut Vec<Coin>) {
+    pub(super) fn sort_by_denom(vec: &mut [Coin]) {
         vec.sort_by(|a, b| a.denom.cmp(&b.denom));
     }
 
@@ -142,6 +949,321 @@ mod helpers {
         map.insert("ibc/1234ABCD".to_string(), Uint128::new(69420));
         map.insert("factory/osmo1234abcd/subdenom".to_string(), Uint128::new(88888));
 
-        Coins(map)
+        Coins::try_from(map).unwrap()
     }
 }
+
+#[test]
+fn zero_policies() {
+    let json = r#"{"uatom":"100","uosmo":"0"}"#;
+    let funds = vec![coin(100, "uatom"), coin(0, "uosmo")];
+
+    let mut de = serde_json::Deserializer::from_str(json);
+    let coins = Coins::deserialize_with_options(&mut de, &ParseOptions::default()).unwrap();
+    assert_eq!(coins.to_string(), "100uatom");
+    let coins = Coins::try_from_iter_with_options(funds.clone(), &ParseOptions::default()).unwrap();
+    assert_eq!(coins.to_string(), "100uatom");
+
+    let reject = ParseOptions {
+        zeros: ZeroPolicy::Reject,
+        ..Default::default()
+    };
+    let mut de = serde_json::Deserializer::from_str(json);
+    let err = Coins::deserialize_with_options(&mut de, &reject).unwrap_err();
+    assert!(err.to_string().contains("zero amount for uosmo"));
+    let err = Coins::try_from_iter_with_options(funds, &reject).unwrap_err();
+    assert_eq!(
+        err,
+        CoinsError::ZeroAmount {
+            denom: "uosmo".to_string()
+        }
+    );
+
+    // non-zero amounts are unaffected
+    let mut de = serde_json::Deserializer::from_str(r#"{"uatom":"100"}"#);
+    let coins = Coins::deserialize_rejecting_zeros(&mut de).unwrap();
+    assert_eq!(coins.to_string(), "100uatom");
+}
+
+#[test]
+fn capping_denoms() {
+    let funds = (0..257)
+        .map(|i| coin(1, format!("denom{}", i)))
+        .collect::<Vec<_>>();
+    let json = serde_json::to_string(
+        &funds
+            .iter()
+            .map(|coin| (&coin.denom, coin.amount))
+            .collect::<BTreeMap<_, _>>(),
+    )
+    .unwrap();
+
+    // at most 256 denoms by default, zero amounts included
+    assert_eq!(Coins::try_from(funds[..256].to_vec()).unwrap().len(), 256);
+    let err = Coins::try_from(funds.clone()).unwrap_err();
+    assert_eq!(
+        err,
+        CoinsError::TooManyDenoms {
+            max: 256
+        }
+    );
+    let mut zeros = funds[..256].to_vec();
+    zeros.push(coin(0, "uatom"));
+    assert!(Coins::try_from(zeros).is_err());
+    let mut de = serde_json::Deserializer::from_str(&json);
+    let err = Coins::deserialize_strict(&mut de).unwrap_err();
+    assert!(err
+        .to_string()
+        .contains("more than the maximum of 256 denoms"));
+
+    // but coins loaded from storage are not capped, as additions may have grown them past the cap
+    let mut grown = Coins::default();
+    for i in 0..300 {
+        grown += coin(1, format!("denom{}", i));
+    }
+    let stored = cosmwasm_std::to_json_vec(&grown).unwrap();
+    assert_eq!(cosmwasm_std::from_json::<Coins>(&stored).unwrap(), grown);
+    assert_eq!(serde_json::from_str::<Coins>(&json).unwrap().len(), 257);
+
+    // the maximum is configurable
+    let options = ParseOptions {
+        max_denoms: 1000,
+        ..Default::default()
+    };
+    assert_eq!(
+        Coins::try_from_iter_with_options(funds.clone(), &options)
+            .unwrap()
+            .len(),
+        257
+    );
+    let mut de = serde_json::Deserializer::from_str(&json);
+    assert_eq!(
+        Coins::deserialize_with_options(&mut de, &options)
+            .unwrap()
+            .len(),
+        257
+    );
+
+    let options = ParseOptions {
+        max_denoms: 1,
+        ..Default::default()
+    };
+    let err = Coins::try_from_iter_with_options(funds[..2].to_vec(), &options).unwrap_err();
+    assert_eq!(
+        err,
+        CoinsError::TooManyDenoms {
+            max: 1
+        }
+    );
+}
+
+#[test]
+fn validating() {
+    let atom = "ibc/27394FB092D2ECCD56123C74F36E4C1F926001CEADA9CA97EA622B25F41E5EB2";
+    let factory = format!("factory/osmo1wd8rd8yp7l5mykgqsmvm5nshhrc2zrcq5w2cm8/{}", "a".repeat(44));
+    let long = "u".repeat(69);
+
+    let valid = vec![coin(1, "uatom"), coin(2, atom), coin(3, &factory), coin(4, "u".repeat(68))];
+    Coins::from_vec(valid.clone(), false)
+        .unwrap()
+        .validate()
+        .unwrap();
+
+    let mut funds = valid;
+    funds.extend([coin(5, "u$d"), coin(6, "ibc/1234"), coin(7, &long), coin(8, "factory/uusdc")]);
+    let err = Coins::from_vec(funds, false)
+        .unwrap()
+        .validate()
+        .unwrap_err();
+
+    // reported in alphabetical order of denom
+    assert_eq!(err.errors.len(), 4);
+    assert!(
+        matches!(&err.errors[0], CoinsError::InvalidDenom { denom, .. } if denom == "factory/uusdc")
+    );
+    assert!(
+        matches!(&err.errors[1], CoinsError::InvalidDenom { denom, .. } if denom == "ibc/1234")
+    );
+    assert_eq!(
+        err.errors[2],
+        CoinsError::InvalidDenom {
+            denom: "u$d".to_string(),
+            reason: "contains characters other than letters, numbers or `/:._-`".to_string(),
+        }
+    );
+    assert_eq!(
+        err.errors[3],
+        CoinsError::LongDenom {
+            denom: long.clone(),
+            len: 69,
+        }
+    );
+    assert!(err
+        .to_string()
+        .starts_with("invalid coins: invalid denom `factory/uusdc`"));
+    assert!(err
+        .to_string()
+        .ends_with(&format!("suspiciously long denom `{}` (69 characters)", long)));
+}
+
+#[test]
+fn validating_denoms_on_deserialization() {
+    let json = r#"{"uatom":"100","u$d":"1"}"#;
+
+    // not validated by default
+    assert_eq!(serde_json::from_str::<Coins>(json).unwrap().len(), 2);
+
+    for (denoms, ok) in [
+        (DenomValidation::Unchecked, true),
+        (DenomValidation::Sdk, false),
+        (DenomValidation::Strict, false),
+    ] {
+        let options = ParseOptions {
+            denoms,
+            ..Default::default()
+        };
+        let mut de = serde_json::Deserializer::from_str(json);
+        assert_eq!(Coins::deserialize_with_options(&mut de, &options).is_ok(), ok);
+    }
+
+    // the difference between SDK and strict validation
+    let json = r#"{"ibc/1234ABCD":"1"}"#;
+    let sdk = ParseOptions {
+        denoms: DenomValidation::Sdk,
+        ..Default::default()
+    };
+    let mut de = serde_json::Deserializer::from_str(json);
+    Coins::deserialize_with_options(&mut de, &sdk).unwrap();
+    let mut de = serde_json::Deserializer::from_str(json);
+    let err = Coins::deserialize_strict(&mut de).unwrap_err();
+    assert!(err.to_string().contains("invalid denom `ibc/1234ABCD`"));
+
+    let mut de = serde_json::Deserializer::from_str(r#"{"uatom":"0"}"#);
+    let err = Coins::deserialize_strict(&mut de).unwrap_err();
+    assert!(err.to_string().contains("zero amount for uatom"));
+
+    // the same options apply to vectors
+    let funds = vec![coin(1, "uatom"), coin(1, "u$d")];
+    assert!(Coins::try_from_iter_with_options(funds, &sdk).is_err());
+}
+
+#[test]
+fn normalizing_ibc_denoms() {
+    let upper = "ibc/27394FB092D2ECCD56123C74F36E4C1F926001CEADA9CA97EA622B25F41E5EB2";
+    let lower = upper.to_lowercase();
+    assert_eq!(normalize_ibc_denom(lower.clone()), upper);
+    assert_eq!(normalize_ibc_denom(upper.to_string()), upper);
+    assert_eq!(
+        normalize_ibc_denom("factory/osmo1abcd/ibc/ab".to_string()),
+        "factory/osmo1abcd/ibc/ab"
+    );
+
+    let options = ParseOptions {
+        normalize_ibc: true,
+        denoms: DenomValidation::Strict,
+        ..Default::default()
+    };
+
+    // lowercase hashes are taken in as their canonical form, and pass strict validation
+    let coins = Coins::try_from_iter_with_options(vec![coin(1, &lower)], &options).unwrap();
+    assert_eq!(coins.get(upper), Some(&Uint128::new(1)));
+    let json = format!(r#"{{"{}":"1"}}"#, lower);
+    let mut de = serde_json::Deserializer::from_str(&json);
+    assert_eq!(Coins::deserialize_with_options(&mut de, &options).unwrap(), coins);
+
+    // the two forms are duplicates of each other
+    let funds = vec![coin(1, upper), coin(2, &lower)];
+    let err = Coins::try_from_iter_with_options(funds.clone(), &options).unwrap_err();
+    assert_eq!(
+        err,
+        CoinsError::DuplicateDenom {
+            denom: upper.to_string()
+        }
+    );
+    let json = format!(r#"{{"{}":"1","{}":"2"}}"#, upper, lower);
+    let mut de = serde_json::Deserializer::from_str(&json);
+    assert!(Coins::deserialize_with_options(&mut de, &options).is_err());
+
+    // not normalized by default
+    assert_eq!(Coins::try_from(funds).unwrap().len(), 2);
+}
+
+#[test]
+fn parsing_separators() {
+    let coins = Coins::from_str("1_000_000uatom,12_3uosmo").unwrap();
+    assert_eq!(coins, Coins::from_str("1000000uatom,123uosmo").unwrap());
+
+    for s in ["1_uatom", "1__0uatom", "_1uatom", "1_000_uatom"] {
+        assert!(Coins::from_str(s).is_err(), "{}", s);
+    }
+    let err = Coins::from_str("1__0uatom").unwrap_err();
+    assert_eq!(
+        err,
+        CoinsError::InvalidAmount {
+            amount: "1__0".to_string()
+        }
+    );
+
+    // JSON amounts are machine-written, and never contain separators
+    assert!(serde_json::from_str::<Coins>(r#"{"uatom":"1_000"}"#).is_err());
+}
+
+#[test]
+fn parsing_strings_with_options() {
+    // by default, denoms are taken as they are, unlike with `from_str`, which checks them against
+    // the SDK's rules
+    let options = ParseOptions::default();
+    let coins = Coins::from_str_with(" 12uatom, 0uosmo, 34ibc/1234,", &options).unwrap();
+    assert_eq!(coins.to_string(), "34ibc/1234,12uatom");
+    assert_eq!(Coins::from_str_with("", &options).unwrap(), Coins::default());
+    assert_eq!(Coins::from_str_with("1ua", &options).unwrap().to_string(), "1ua");
+    assert!(Coins::from_str("1ua").is_err());
+
+    // strictness of denom validation
+    let strict = ParseOptions {
+        denoms: DenomValidation::Strict,
+        ..Default::default()
+    };
+    let err = Coins::from_str_with("34ibc/1234", &strict).unwrap_err();
+    assert!(matches!(err, CoinsError::InvalidDenom { .. }));
+    assert_eq!(
+        Coins::from_str_with("12uatom", &strict).unwrap(),
+        Coins::from_str_strict("12uatom").unwrap()
+    );
+
+    // maximum number of entries, zero amounts included
+    let capped = ParseOptions {
+        max_denoms: 2,
+        ..Default::default()
+    };
+    assert_eq!(
+        Coins::from_str_with("1uatom,2uosmo", &capped)
+            .unwrap()
+            .len(),
+        2
+    );
+    let err = Coins::from_str_with("1uatom,2uosmo,0ujuno", &capped).unwrap_err();
+    assert_eq!(
+        err,
+        CoinsError::TooManyDenoms {
+            max: 2
+        }
+    );
+
+    // empty input, including input that is all zeros
+    let non_empty = ParseOptions {
+        allow_empty: false,
+        ..Default::default()
+    };
+    assert_eq!(Coins::from_str_with(" ", &non_empty).unwrap_err(), CoinsError::Empty);
+    assert_eq!(Coins::from_str_with("0uatom", &non_empty).unwrap_err(), CoinsError::Empty);
+    assert_eq!(
+        Coins::try_from_iter_with_options(vec![coin(0, "uatom")], &non_empty).unwrap_err(),
+        CoinsError::Empty
+    );
+    let mut de = serde_json::Deserializer::from_str("{}");
+    let err = Coins::deserialize_with_options(&mut de, &non_empty).unwrap_err();
+    assert!(err
+        .to_string()
+        .contains("coins must contain at least one non-zero amount"));
+}